@@ -0,0 +1,452 @@
+use std::collections::{BTreeMap, HashSet, VecDeque};
+
+use crate::classes::{Archetype, ClassData};
+use crate::client::{default_client, WynnClient};
+use crate::decode::{into_keyed_vec, Keyed};
+use crate::endpoint::Endpoint;
+use crate::error::{Result, WynnError};
+use crate::identifier::PlayerIdentifier;
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Ability points a character has once every source of them is unlocked.
+pub const MAX_ABILITY_POINTS: i32 = 45;
+
+/// A class's ability tree as a graph, nodes are linked from parent to child.
+#[derive(Debug, Deserialize)]
+#[serde(from = "RawAbilityTree")]
+pub struct AbilityTree {
+    pub class: String,
+    pub archetypes: Vec<AbilityArchetype>,
+    pub nodes: Vec<AbilityNode>,
+    pub connectors: Vec<Connector>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AbilityArchetype {
+    // Lowercased so it matches the archetype ids from `get_class`.
+    #[serde(skip)]
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub short_description: String,
+    pub slot: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AbilityNode {
+    pub id: String,
+    pub name: String,
+    pub archetype: Option<String>,
+    pub cost: i32,
+    pub required_archetype: Option<ArchetypeRequirement>,
+    pub required_ability: Option<String>,
+    // Abilities that can no longer be taken once this one is.
+    pub blocks: Vec<String>,
+    pub parents: Vec<String>,
+    pub children: Vec<String>,
+    pub page: i32,
+    pub coordinates: Coordinates,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchetypeRequirement {
+    pub archetype: String,
+    pub points: i32,
+}
+
+/// The lines drawn between abilities, they carry no rules of their own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Connector {
+    pub id: String,
+    pub page: i32,
+    pub coordinates: Coordinates,
+}
+
+/// The abilities a character has unlocked, as ids into its class's `AbilityTree`.
+#[derive(Debug, Deserialize)]
+#[serde(from = "Vec<RawMapEntry>")]
+pub struct AbilityMap {
+    pub unlocked: Vec<String>,
+}
+
+/// A rule an ability allocation breaks.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AllocationError {
+    #[error("`{0}` is not in the ability tree")]
+    UnknownAbility(String),
+    #[error("{spent} ability points spent out of {budget}")]
+    OverBudget { spent: i32, budget: i32 },
+    #[error("`{0}` is not connected to the start of the tree")]
+    Disconnected(String),
+    #[error("`{ability}` needs `{required}`")]
+    MissingAbility { ability: String, required: String },
+    #[error("`{ability}` needs {required} {archetype} abilities, {unlocked} are unlocked")]
+    ArchetypeMinimum {
+        ability: String,
+        archetype: String,
+        required: i32,
+        unlocked: i32,
+    },
+    #[error("`{ability}` blocks `{blocked}`")]
+    Blocked { ability: String, blocked: String },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct Coordinates {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl AbilityArchetype {
+    pub fn class_archetype<'a>(&self, class: &'a ClassData) -> Option<&'a Archetype> {
+        class.archetype(&self.id)
+    }
+}
+
+impl AbilityTree {
+    pub fn node(&self, id: &str) -> Option<&AbilityNode> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    pub fn archetype(&self, id: &str) -> Option<&AbilityArchetype> {
+        self.archetypes
+            .iter()
+            .find(|archetype| archetype.id.eq_ignore_ascii_case(id))
+    }
+
+    pub fn archetype_nodes(&self, archetype: &str) -> Vec<&AbilityNode> {
+        self.nodes
+            .iter()
+            .filter(|node| {
+                node.archetype
+                    .as_deref()
+                    .is_some_and(|id| id.eq_ignore_ascii_case(archetype))
+            })
+            .collect()
+    }
+
+    /// Every node with a path down to `id`, closest first.
+    pub fn ancestors(&self, id: &str) -> Vec<&AbilityNode> {
+        let mut seen = HashSet::from([id]);
+        let mut queue = VecDeque::from([id]);
+        let mut ancestors = Vec::new();
+
+        while let Some(id) = queue.pop_front() {
+            let Some(node) = self.node(id) else {
+                continue;
+            };
+            for parent in &node.parents {
+                if seen.insert(parent) {
+                    if let Some(parent) = self.node(parent) {
+                        ancestors.push(parent);
+                    }
+                    queue.push_back(parent);
+                }
+            }
+        }
+
+        ancestors
+    }
+
+    /// The nodes that could be taken next given the ones already `unlocked`.
+    ///
+    /// A node is reachable when it has no parents or one of them is unlocked, its required
+    /// ability is unlocked and nothing unlocked blocks it. Ability and archetype points are not
+    /// counted.
+    pub fn reachable(&self, unlocked: &[&str]) -> Vec<&AbilityNode> {
+        let unlocked: HashSet<&str> = unlocked.iter().copied().collect();
+
+        self.nodes
+            .iter()
+            .filter(|node| !unlocked.contains(node.id.as_str()))
+            .filter(|node| {
+                node.parents.is_empty()
+                    || node
+                        .parents
+                        .iter()
+                        .any(|parent| unlocked.contains(parent.as_str()))
+            })
+            .filter(|node| {
+                node.required_ability
+                    .as_deref()
+                    .is_none_or(|required| unlocked.contains(required))
+            })
+            .filter(|node| !self.is_blocked(node, &unlocked))
+            .collect()
+    }
+
+    /// Checks an allocation against every rule of the tree, returning each one it breaks.
+    pub fn validate_allocation(
+        &self,
+        unlocked: &[&str],
+        budget: i32,
+    ) -> std::result::Result<(), Vec<AllocationError>> {
+        let mut errors: Vec<AllocationError> = unlocked
+            .iter()
+            .filter(|id| self.node(id).is_none())
+            .map(|id| AllocationError::UnknownAbility(id.to_string()))
+            .collect();
+        let unlocked: HashSet<&str> = unlocked.iter().copied().collect();
+        // Tree order keeps the report the same however the allocation is listed.
+        let nodes: Vec<&AbilityNode> = self
+            .nodes
+            .iter()
+            .filter(|node| unlocked.contains(node.id.as_str()))
+            .collect();
+
+        let spent: i32 = nodes.iter().map(|node| node.cost).sum();
+        if spent > budget {
+            errors.push(AllocationError::OverBudget { spent, budget });
+        }
+
+        // Walk down from the unlocked roots through unlocked nodes only.
+        let mut connected: HashSet<&str> = HashSet::new();
+        let mut queue: VecDeque<&AbilityNode> = nodes
+            .iter()
+            .copied()
+            .filter(|node| node.parents.is_empty())
+            .collect();
+        while let Some(node) = queue.pop_front() {
+            if !connected.insert(node.id.as_str()) {
+                continue;
+            }
+            for child in &node.children {
+                if unlocked.contains(child.as_str()) {
+                    queue.extend(self.node(child));
+                }
+            }
+        }
+
+        for node in &nodes {
+            if !connected.contains(node.id.as_str()) {
+                errors.push(AllocationError::Disconnected(node.id.clone()));
+            }
+            if let Some(required) = &node.required_ability {
+                if !unlocked.contains(required.as_str()) {
+                    errors.push(AllocationError::MissingAbility {
+                        ability: node.id.clone(),
+                        required: required.clone(),
+                    });
+                }
+            }
+            if let Some(requirement) = &node.required_archetype {
+                let count = nodes
+                    .iter()
+                    .filter(|other| other.id != node.id)
+                    .filter(|other| other.archetype.as_ref() == Some(&requirement.archetype))
+                    .count();
+                let count = i32::try_from(count).unwrap_or(i32::MAX);
+                if count < requirement.points {
+                    errors.push(AllocationError::ArchetypeMinimum {
+                        ability: node.id.clone(),
+                        archetype: requirement.archetype.clone(),
+                        required: requirement.points,
+                        unlocked: count,
+                    });
+                }
+            }
+            for blocked in &node.blocks {
+                if unlocked.contains(blocked.as_str()) {
+                    errors.push(AllocationError::Blocked {
+                        ability: node.id.clone(),
+                        blocked: blocked.clone(),
+                    });
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    // Blocking goes both ways, the tree doesn't always list it on both nodes.
+    pub(crate) fn is_blocked(&self, node: &AbilityNode, unlocked: &HashSet<&str>) -> bool {
+        node.blocks
+            .iter()
+            .any(|blocked| unlocked.contains(blocked.as_str()))
+            || unlocked.iter().any(|id| {
+                self.node(id)
+                    .is_some_and(|other| other.blocks.contains(&node.id))
+            })
+    }
+}
+
+impl Keyed for AbilityArchetype {
+    fn set_key(&mut self, key: String) {
+        self.id = key.to_lowercase();
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct RawAbilityTree {
+    archetypes: BTreeMap<String, AbilityArchetype>,
+    pages: BTreeMap<i32, BTreeMap<String, RawTreeEntry>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTreeEntry {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    archetype: Option<String>,
+    #[serde(default)]
+    coordinates: Coordinates,
+    #[serde(default)]
+    requirements: RawRequirements,
+    #[serde(default)]
+    links: Vec<String>,
+    #[serde(default)]
+    locks: Vec<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "SCREAMING_SNAKE_CASE")]
+struct RawRequirements {
+    ability_points: i32,
+    node: Option<String>,
+    archetype: Option<RawArchetypeRequirement>,
+}
+
+#[derive(Deserialize)]
+struct RawArchetypeRequirement {
+    name: String,
+    amount: i32,
+}
+
+#[derive(Deserialize)]
+struct RawMapEntry {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    id: String,
+}
+
+impl From<Vec<RawMapEntry>> for AbilityMap {
+    fn from(entries: Vec<RawMapEntry>) -> Self {
+        AbilityMap {
+            // Connectors are listed too, only the abilities matter.
+            unlocked: entries
+                .into_iter()
+                .filter(|entry| entry.kind == "ability")
+                .map(|entry| entry.id)
+                .collect(),
+        }
+    }
+}
+
+impl AbilityMap {
+    pub fn ids(&self) -> Vec<&str> {
+        self.unlocked.iter().map(String::as_str).collect()
+    }
+}
+
+impl From<RawAbilityTree> for AbilityTree {
+    fn from(raw: RawAbilityTree) -> Self {
+        let mut nodes = Vec::new();
+        let mut connectors = Vec::new();
+
+        for (page, entries) in raw.pages {
+            for (id, entry) in entries {
+                if entry.kind == "connector" {
+                    connectors.push(Connector {
+                        id,
+                        page,
+                        coordinates: entry.coordinates,
+                    });
+                    continue;
+                }
+
+                nodes.push(AbilityNode {
+                    id,
+                    name: entry.name,
+                    archetype: entry.archetype.map(|archetype| archetype.to_lowercase()),
+                    cost: entry.requirements.ability_points,
+                    required_archetype: entry.requirements.archetype.map(|requirement| {
+                        ArchetypeRequirement {
+                            archetype: requirement.name.to_lowercase(),
+                            points: requirement.amount,
+                        }
+                    }),
+                    required_ability: entry.requirements.node,
+                    blocks: entry.locks,
+                    parents: Vec::new(),
+                    children: entry.links,
+                    page,
+                    coordinates: entry.coordinates,
+                });
+            }
+        }
+
+        // Only children are listed, parents are filled in from them.
+        let links: Vec<(String, String)> = nodes
+            .iter()
+            .flat_map(|node| {
+                node.children
+                    .iter()
+                    .map(move |child| (node.id.clone(), child.clone()))
+            })
+            .collect();
+        for (parent, child) in links {
+            if let Some(node) = nodes.iter_mut().find(|node| node.id == child) {
+                node.parents.push(parent);
+            }
+        }
+
+        AbilityTree {
+            // Filled in from the class that was requested.
+            class: String::new(),
+            archetypes: into_keyed_vec(raw.archetypes),
+            nodes,
+            connectors,
+        }
+    }
+}
+
+impl WynnClient {
+    pub async fn get_ability_tree(&self, class: &str) -> Result<AbilityTree> {
+        let mut tree: AbilityTree = self.get(Endpoint::new(["ability", "tree", class])).await?;
+        tree.class = class.to_string();
+
+        Ok(tree)
+    }
+
+    pub async fn get_character_ability_map(
+        &self,
+        identifier: impl TryInto<PlayerIdentifier, Error: Into<WynnError>>,
+        character_uuid: &str,
+    ) -> Result<AbilityMap> {
+        let identifier: PlayerIdentifier = identifier.try_into().map_err(Into::into)?;
+
+        self.get(Endpoint::new([
+            "player",
+            identifier.as_str(),
+            "characters",
+            character_uuid,
+            "abilities",
+        ]))
+        .await
+    }
+}
+
+pub async fn get_ability_tree(class: &str) -> Result<AbilityTree> {
+    default_client()?.get_ability_tree(class).await
+}
+
+pub async fn get_character_ability_map(
+    identifier: impl TryInto<PlayerIdentifier, Error: Into<WynnError>>,
+    character_uuid: &str,
+) -> Result<AbilityMap> {
+    default_client()?
+        .get_character_ability_map(identifier, character_uuid)
+        .await
+}
@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use bytes::Bytes;
+use reqwest::header::{HeaderMap, CACHE_CONTROL, DATE, EXPIRES};
+
+use crate::endpoint::Endpoint;
+
+/// Settings for the opt-in in-memory response cache.
+///
+/// Responses are kept for as long as their `Cache-Control: max-age` or `Expires` headers allow.
+/// A TTL override for a plain text path prefix (such as `/classes` or `/guild/Salt Mines`) takes
+/// precedence over the headers, and
+/// `default_ttl` is used for responses that carry neither.
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    pub max_entries: usize,
+    pub max_bytes: usize,
+    pub default_ttl: Option<Duration>,
+    pub ttl_overrides: HashMap<String, Duration>,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            max_entries: 1024,
+            max_bytes: 64 * 1024 * 1024,
+            default_ttl: None,
+            ttl_overrides: HashMap::new(),
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    pub fn default_ttl(mut self, default_ttl: Duration) -> Self {
+        self.default_ttl = Some(default_ttl);
+        self
+    }
+
+    pub fn ttl_override(mut self, path_prefix: impl Into<String>, ttl: Duration) -> Self {
+        self.ttl_overrides.insert(path_prefix.into(), ttl);
+        self
+    }
+
+    // The longest matching prefix wins so `/guild/list` can differ from `/guild`. Prefixes are
+    // plain text like the cache paths on `WynnClient`, and are encoded to match request paths.
+    fn ttl_override_for(&self, path: &str) -> Option<Duration> {
+        self.ttl_overrides
+            .iter()
+            .filter_map(|(prefix, ttl)| Some((Endpoint::from_path(prefix).path().ok()?, *ttl)))
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, ttl)| ttl)
+    }
+
+    pub(crate) fn ttl_for(&self, path: &str, headers: &HeaderMap) -> Option<Duration> {
+        let ttl = match self.ttl_override_for(path) {
+            Some(ttl) => Some(ttl),
+            None => match header_ttl(headers) {
+                Some(ttl) => ttl,
+                None => self.default_ttl,
+            },
+        };
+
+        ttl.filter(|ttl| !ttl.is_zero())
+    }
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    body: Bytes,
+    inserted_at: Instant,
+    expires_at: Instant,
+}
+
+#[derive(Debug)]
+pub(crate) struct ResponseCache {
+    config: CacheConfig,
+    entries: Mutex<HashMap<String, CacheEntry>>,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(config: CacheConfig) -> Self {
+        ResponseCache {
+            config,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn get(&self, url: &str) -> Option<Bytes> {
+        let mut entries = self.entries.lock().unwrap();
+
+        match entries.get(url) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.body.clone()),
+            Some(_) => {
+                entries.remove(url);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub(crate) fn insert(&self, url: &str, path: &str, headers: &HeaderMap, body: &Bytes) {
+        let Some(ttl) = self.config.ttl_for(path, headers) else {
+            return;
+        };
+        if body.len() > self.config.max_bytes || self.config.max_entries == 0 {
+            return;
+        }
+
+        let now = Instant::now();
+        // A TTL too large to represent is treated as a bad header rather than "forever".
+        let Some(expires_at) = now.checked_add(ttl) else {
+            return;
+        };
+        let mut entries = self.entries.lock().unwrap();
+        entries.insert(
+            url.to_string(),
+            CacheEntry {
+                body: body.clone(),
+                inserted_at: now,
+                expires_at,
+            },
+        );
+
+        entries.retain(|_, entry| entry.expires_at > now);
+        while entries.len() > self.config.max_entries
+            || entries
+                .values()
+                .map(|entry| entry.body.len())
+                .sum::<usize>()
+                > self.config.max_bytes
+        {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.inserted_at)
+                .map(|(key, _)| key.clone());
+
+            match oldest {
+                Some(key) => entries.remove(&key),
+                None => break,
+            };
+        }
+    }
+
+    pub(crate) fn invalidate(&self, url_prefix: &str) {
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|url, _| !url.starts_with(url_prefix));
+    }
+
+    pub(crate) fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+}
+
+// `None` means the headers say nothing about caching, `Some(None)` that the response must not be
+// cached at all.
+fn header_ttl(headers: &HeaderMap) -> Option<Option<Duration>> {
+    if let Some(cache_control) = headers.get(CACHE_CONTROL).and_then(|v| v.to_str().ok()) {
+        let directives: Vec<String> = cache_control
+            .split(',')
+            .map(|directive| directive.trim().to_ascii_lowercase())
+            .collect();
+
+        if directives
+            .iter()
+            .any(|directive| directive == "no-store" || directive == "no-cache")
+        {
+            return Some(None);
+        }
+        if let Some(max_age) = directives
+            .iter()
+            .find_map(|directive| directive.strip_prefix("max-age="))
+        {
+            return Some(
+                max_age
+                    .trim_matches('"')
+                    .parse::<u64>()
+                    .ok()
+                    .map(Duration::from_secs),
+            );
+        }
+    }
+
+    let expires = headers
+        .get(EXPIRES)
+        .and_then(|v| v.to_str().ok())
+        .map(httpdate::parse_http_date)?;
+    let expires = match expires {
+        Ok(expires) => expires,
+        // An invalid Expires value means the response is already stale.
+        Err(_) => return Some(None),
+    };
+    let date = headers
+        .get(DATE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| httpdate::parse_http_date(v).ok())
+        .unwrap_or_else(SystemTime::now);
+
+    Some(expires.duration_since(date).ok())
+}
@@ -1,24 +1,33 @@
-use anyhow::Context;
-use serde_json::Value;
-
-#[derive(Debug)]
+use crate::client::{default_client, WynnClient};
+use crate::decode::{into_keyed_vec, keyed_vec, Keyed};
+use crate::endpoint::Endpoint;
+use crate::error::Result;
+use serde::Deserialize;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct SimpleClass {
+    #[serde(skip)]
     pub id: String,
     pub name: String,
     pub overall_difficulty: i32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ClassData {
     pub id: String,
     pub name: String,
     pub lore: String,
     pub overall_difficulty: i32,
+    #[serde(deserialize_with = "keyed_vec")]
     pub archetypes: Vec<Archetype>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct Archetype {
+    #[serde(skip)]
     pub id: String,
     pub name: String,
     pub difficulty: i32,
@@ -28,132 +37,42 @@ pub struct Archetype {
     pub speed: i32,
 }
 
-pub async fn get_class(id: &str) -> anyhow::Result<ClassData> {
-    let url = format!("https://api.wynncraft.com/v3/classes/{}", id);
-
-    let response = reqwest::get(url)
-        .await
-        .context("Failed to make the API request")?;
-    let data: Value = response
-        .json()
-        .await
-        .context("Failed to parse the JSON response")?;
-
-    let class_object = data
-        .as_object()
-        .ok_or_else(|| anyhow::Error::msg("Expected the JSON to be an object"))?;
-
-    let id = class_object["id"]
-        .as_str()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'id' to be a String"))?
-        .parse::<String>()
-        .unwrap();
-
-    let name = class_object["name"]
-        .as_str()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'name' to be a String"))?
-        .parse::<String>()
-        .unwrap();
-
-    let lore = class_object["lore"]
-        .as_str()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'lore' to be a String"))?
-        .parse::<String>()
-        .unwrap();
-
-    let overall_difficulty = class_object["overallDifficulty"]
-        .as_i64()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'overallDifficulty' to be an integer"))?
-        as i32;
-
-    let mut archetypes = Vec::new();
-    let archetypes_object = class_object
-        .get("archetypes")
-        .ok_or_else(|| anyhow::Error::msg("Expected 'archetypes' to be a JSON object"))?
-        .as_object()
-        .unwrap();
-
-    for (archetype_id, archetype_details) in archetypes_object {
-        let archetype_name = archetype_details
-            .get("name")
-            .and_then(Value::as_str)
-            .unwrap_or_default()
-            .to_string();
-        let difficulty = archetype_details
-            .get("difficulty")
-            .and_then(Value::as_i64)
-            .unwrap_or_default() as i32;
-        let damage = archetype_details
-            .get("damage")
-            .and_then(Value::as_i64)
-            .unwrap_or_default() as i32;
-        let defence = archetype_details
-            .get("defence")
-            .and_then(Value::as_i64)
-            .unwrap_or_default() as i32;
-        let range = archetype_details
-            .get("range")
-            .and_then(Value::as_i64)
-            .unwrap_or_default() as i32;
-        let speed = archetype_details
-            .get("speed")
-            .and_then(Value::as_i64)
-            .unwrap_or_default() as i32;
-
-        archetypes.push(Archetype {
-            id: archetype_id.to_string(),
-            name: archetype_name,
-            difficulty,
-            damage,
-            defence,
-            range,
-            speed,
-        });
+impl ClassData {
+    pub fn archetype(&self, id: &str) -> Option<&Archetype> {
+        self.archetypes
+            .iter()
+            .find(|archetype| archetype.id.eq_ignore_ascii_case(id))
     }
+}
 
-    Ok(ClassData {
-        id,
-        name,
-        lore,
-        overall_difficulty,
-        archetypes,
-    })
+impl Keyed for SimpleClass {
+    fn set_key(&mut self, key: String) {
+        self.id = key;
+    }
 }
 
-pub async fn get_classes() -> anyhow::Result<Vec<SimpleClass>> {
-    let url = "https://api.wynncraft.com/v3/classes";
+impl Keyed for Archetype {
+    fn set_key(&mut self, key: String) {
+        self.id = key;
+    }
+}
 
-    let response = reqwest::get(url)
-        .await
-        .context("Failed to make the API request")?;
-    let data: Value = response
-        .json()
-        .await
-        .context("Failed to parse the JSON response")?;
+impl WynnClient {
+    pub async fn get_class(&self, id: &str) -> Result<ClassData> {
+        self.get(Endpoint::new(["classes", id])).await
+    }
 
-    let classes_object = data
-        .as_object()
-        .ok_or_else(|| anyhow::Error::msg("Expected the JSON to be an object"))?;
+    pub async fn get_classes(&self) -> Result<Vec<SimpleClass>> {
+        let classes = self.get(Endpoint::new(["classes"])).await?;
 
-    let mut class_data = Vec::new();
-    for (id, details) in classes_object {
-        if let Some(class_details) = details.as_object() {
-            let name = class_details
-                .get("name")
-                .and_then(Value::as_str)
-                .unwrap_or_default()
-                .to_string();
-            let overall_difficulty = class_details
-                .get("overallDifficulty")
-                .and_then(Value::as_i64)
-                .unwrap_or_default() as i32;
-            class_data.push(SimpleClass {
-                id: id.clone(),
-                name,
-                overall_difficulty,
-            });
-        }
+        Ok(into_keyed_vec(classes))
     }
+}
+
+pub async fn get_class(id: &str) -> Result<ClassData> {
+    default_client()?.get_class(id).await
+}
 
-    Ok(class_data)
+pub async fn get_classes() -> Result<Vec<SimpleClass>> {
+    default_client()?.get_classes().await
 }
@@ -0,0 +1,458 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use bytes::Bytes;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, RETRY_AFTER, USER_AGENT};
+use reqwest::{RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::cache::{CacheConfig, ResponseCache};
+use crate::decode::decode;
+use crate::disk_cache::{DiskCache, StoredResponse};
+use crate::endpoint::Endpoint;
+use crate::error::{MultipleChoices, Result, WynnError};
+use crate::rate_limit::{RateLimit, RateLimiter};
+use crate::retry::RetryPolicy;
+
+pub const DEFAULT_BASE_URL: &str = "https://api.wynncraft.com/v3";
+pub const DEFAULT_USER_AGENT: &str = concat!("antikythera_rs/", env!("CARGO_PKG_VERSION"));
+pub const API_KEY_ENV: &str = "WYNNCRAFT_API_KEY";
+
+/// A handle to the Wynncraft API.
+///
+/// Cloning is cheap and clones share the same connection pool, so build one client and pass it
+/// around rather than creating a new one per request.
+#[derive(Debug, Clone)]
+pub struct WynnClient {
+    http: reqwest::Client,
+    base_url: String,
+    rate_limiter: Arc<RateLimiter>,
+    retry_policy: RetryPolicy,
+    cache: Option<Arc<ResponseCache>>,
+    disk_cache: Option<Arc<DiskCache>>,
+    offline: bool,
+    authenticated: bool,
+}
+
+#[derive(Debug)]
+pub struct WynnClientBuilder {
+    base_url: String,
+    user_agent: String,
+    headers: HeaderMap,
+    timeout: Option<Duration>,
+    wait_on_rate_limit: bool,
+    retry_policy: RetryPolicy,
+    cache: Option<CacheConfig>,
+    disk_cache: Option<PathBuf>,
+    offline: bool,
+    api_key: Option<String>,
+}
+
+impl Default for WynnClientBuilder {
+    fn default() -> Self {
+        WynnClientBuilder {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            headers: HeaderMap::new(),
+            timeout: None,
+            wait_on_rate_limit: false,
+            retry_policy: RetryPolicy::none(),
+            cache: None,
+            disk_cache: None,
+            offline: false,
+            api_key: None,
+        }
+    }
+}
+
+impl WynnClientBuilder {
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    pub fn default_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    /// Sends `Authorization: Bearer <key>` with every request, which raises the rate limits and
+    /// unlocks authenticated endpoints.
+    pub fn api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    /// Reads the API key from the `WYNNCRAFT_API_KEY` environment variable, leaving the client
+    /// unauthenticated if it is unset or empty.
+    pub fn api_key_from_env(mut self) -> Self {
+        if let Some(api_key) = std::env::var(API_KEY_ENV)
+            .ok()
+            .filter(|key| !key.is_empty())
+        {
+            self.api_key = Some(api_key);
+        }
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Wait for the rate limit bucket to reset instead of sending a request that the API would
+    /// reject with 429.
+    pub fn wait_on_rate_limit(mut self, wait: bool) -> Self {
+        self.wait_on_rate_limit = wait;
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Stores every response in `directory` so it can be reused after a restart. Stored responses
+    /// are served while they are fresh according to the same rules as the in-memory cache.
+    pub fn disk_cache(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.disk_cache = Some(directory.into());
+        self
+    }
+
+    /// Serves every request from the disk cache, stale or not, and never contacts the API. A
+    /// request with no stored response fails with [`WynnError::Offline`].
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    pub fn build(self) -> Result<WynnClient> {
+        let mut headers = self.headers;
+        if !headers.contains_key(USER_AGENT) {
+            let user_agent = HeaderValue::from_str(&self.user_agent).map_err(|_| {
+                WynnError::Config("Expected the user agent to be a valid header value".to_string())
+            })?;
+            headers.insert(USER_AGENT, user_agent);
+        }
+
+        let authenticated = self.api_key.is_some();
+        if let Some(api_key) = &self.api_key {
+            let mut authorization = HeaderValue::from_str(&format!("Bearer {}", api_key.trim()))
+                .map_err(|_| {
+                    WynnError::Config("Expected the API key to be a valid header value".to_string())
+                })?;
+            authorization.set_sensitive(true);
+            headers.insert(AUTHORIZATION, authorization);
+        }
+
+        let mut http = reqwest::Client::builder().default_headers(headers);
+        if let Some(timeout) = self.timeout {
+            http = http.timeout(timeout);
+        }
+
+        Ok(WynnClient {
+            http: http.build()?,
+            base_url: self.base_url.trim_end_matches('/').to_string(),
+            rate_limiter: Arc::new(RateLimiter::new(self.wait_on_rate_limit)),
+            retry_policy: self.retry_policy,
+            cache: self
+                .cache
+                .clone()
+                .map(|config| Arc::new(ResponseCache::new(config))),
+            disk_cache: self.disk_cache.map(|directory| {
+                Arc::new(DiskCache::new(directory, self.cache.unwrap_or_default()))
+            }),
+            offline: self.offline,
+            authenticated,
+        })
+    }
+}
+
+impl Default for WynnClient {
+    fn default() -> Self {
+        WynnClient::new()
+    }
+}
+
+impl WynnClient {
+    /// Creates a client for the public API with the default settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the TLS backend cannot be initialised, use [`WynnClient::builder`] to handle
+    /// that as an error instead.
+    pub fn new() -> Self {
+        WynnClient::builder()
+            .build()
+            .expect("Failed to build the default client")
+    }
+
+    pub fn builder() -> WynnClientBuilder {
+        WynnClientBuilder::default()
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// The last known budget for a rate limit bucket, such as `player` or `guild`.
+    pub fn rate_limit(&self, bucket: &str) -> Option<RateLimit> {
+        self.rate_limiter.get(bucket)
+    }
+
+    pub fn rate_limits(&self) -> HashMap<String, RateLimit> {
+        self.rate_limiter.all()
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Drops every cached response whose path starts with `path_prefix`, for example `/guild`.
+    ///
+    /// Paths here and in the stored response methods are plain text, such as
+    /// `/guild/Salt Mines`, and are encoded the same way as the requests.
+    pub fn invalidate(&self, path_prefix: &str) {
+        if let (Some(cache), Ok(url)) = (&self.cache, self.url_for(path_prefix)) {
+            cache.invalidate(&url);
+        }
+    }
+
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+    }
+
+    pub fn cached_responses(&self) -> usize {
+        self.cache.as_ref().map_or(0, |cache| cache.len())
+    }
+
+    pub fn is_authenticated(&self) -> bool {
+        self.authenticated
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// The response stored on disk for `path`, such as `/guild/list/territory`, if any.
+    pub async fn stored_response(&self, path: &str) -> Option<StoredResponse> {
+        let url = self.url_for(path).ok()?;
+        self.disk_cache.as_ref()?.get(&url).await
+    }
+
+    /// Deletes every response stored on disk whose path starts with `path_prefix`.
+    pub async fn invalidate_stored(&self, path_prefix: &str) -> Result<()> {
+        if let Some(disk_cache) = &self.disk_cache {
+            disk_cache.invalidate(&self.url_for(path_prefix)?).await?;
+        }
+
+        Ok(())
+    }
+
+    fn url_for(&self, path: &str) -> Result<String> {
+        Ok(format!(
+            "{}{}",
+            self.base_url,
+            Endpoint::from_path(path).path()?
+        ))
+    }
+
+    pub(crate) async fn get<T: DeserializeOwned>(&self, endpoint: Endpoint) -> Result<T> {
+        decode(&self.get_body(endpoint).await?)
+    }
+
+    pub(crate) async fn get_body(&self, endpoint: Endpoint) -> Result<Bytes> {
+        let path = endpoint.path()?;
+        let url = format!("{}{}", self.base_url, path);
+
+        match self.cached(&url).await {
+            Some(body) => Ok(body),
+            None if self.offline => Err(WynnError::Offline { url }),
+            None => self.fetch(&url, &path).await,
+        }
+    }
+
+    async fn cached(&self, url: &str) -> Option<Bytes> {
+        if let Some(body) = self.cache.as_ref().and_then(|cache| cache.get(url)) {
+            return Some(body);
+        }
+
+        let stored = self.disk_cache.as_ref()?.get(url).await?;
+        if self.offline || stored.is_fresh() {
+            Some(stored.body)
+        } else {
+            None
+        }
+    }
+
+    async fn fetch(&self, url: &str, path: &str) -> Result<Bytes> {
+        let (headers, body) = self.send_with_retry(path, || self.http.get(url)).await?;
+
+        if let Some(cache) = &self.cache {
+            cache.insert(url, path, &headers, &body);
+        }
+        if let Some(disk_cache) = &self.disk_cache {
+            disk_cache.insert(url, path, &headers, &body).await;
+        }
+
+        Ok(body)
+    }
+
+    // Searches are sent as a JSON body, so their responses bypass both caches.
+    pub(crate) async fn post<T: DeserializeOwned>(
+        &self,
+        endpoint: Endpoint,
+        body: &(impl Serialize + ?Sized),
+    ) -> Result<T> {
+        let path = endpoint.path()?;
+        let url = format!("{}{}", self.base_url, path);
+        if self.offline {
+            return Err(WynnError::Offline { url });
+        }
+
+        let (_, body) = self
+            .send_with_retry(&path, || self.http.post(&url).json(body))
+            .await?;
+        decode(&body)
+    }
+
+    // `request` builds a fresh request for every attempt since a sent request can't be reused.
+    async fn send_with_retry(
+        &self,
+        path: &str,
+        request: impl Fn() -> RequestBuilder,
+    ) -> Result<(HeaderMap, Bytes)> {
+        let bucket = RateLimiter::bucket(path);
+
+        let mut attempt = 1;
+        loop {
+            match self.send(request(), &bucket).await {
+                Ok(response) => return Ok(response),
+                Err(e)
+                    if attempt < self.retry_policy.max_attempts
+                        && self.retry_policy.should_retry(&e) =>
+                {
+                    tokio::time::sleep(self.retry_policy.delay(attempt, &e)).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn send(&self, request: RequestBuilder, bucket: &str) -> Result<(HeaderMap, Bytes)> {
+        self.rate_limiter.acquire(bucket).await;
+        let response = request.send().await?;
+        self.rate_limiter.update(bucket, response.headers());
+
+        let response = check_status(response).await?;
+        let headers = response.headers().clone();
+        Ok((headers, response.bytes().await?))
+    }
+}
+
+async fn check_status(response: Response) -> Result<Response> {
+    let status = response.status();
+
+    match status {
+        StatusCode::OK => Ok(response),
+        StatusCode::MULTIPLE_CHOICES => Err(multiple_choices(response).await),
+        StatusCode::NOT_FOUND => Err(WynnError::NotFound),
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(WynnError::Unauthorized {
+            status: status.as_u16(),
+            message: error_message(response).await,
+        }),
+        StatusCode::TOO_MANY_REQUESTS => Err(WynnError::RateLimited {
+            retry_after: retry_after(&response),
+        }),
+        _ if status.is_client_error() => Err(WynnError::ClientError {
+            status: status.as_u16(),
+            message: error_message(response).await,
+        }),
+        _ if status.is_server_error() => Err(WynnError::ServerError {
+            status: status.as_u16(),
+        }),
+        _ if status.is_success() => Ok(response),
+        _ => Err(WynnError::UnexpectedStatus {
+            status: status.as_u16(),
+        }),
+    }
+}
+
+// The API answers ambiguous player and guild lookups with 300 and a map of the candidates.
+async fn multiple_choices(response: Response) -> WynnError {
+    let body = match response.bytes().await {
+        Ok(body) => body,
+        Err(e) => return e.into(),
+    };
+
+    match decode::<MultipleChoices>(&body) {
+        Ok(choices) => WynnError::MultipleChoices(choices),
+        Err(e) => e,
+    }
+}
+
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+// Error responses look like `{"Error": "..."}`, fall back to the raw body if they don't.
+async fn error_message(response: Response) -> Option<String> {
+    let body = response.text().await.ok()?;
+    let message = serde_json::from_str::<Value>(&body)
+        .ok()
+        .and_then(|data| {
+            data.get("Error")
+                .or_else(|| data.get("error"))
+                .and_then(Value::as_str)
+                .map(|s| s.to_string())
+        })
+        .unwrap_or(body);
+
+    if message.is_empty() {
+        None
+    } else {
+        Some(message)
+    }
+}
+
+static DEFAULT_CLIENT: OnceLock<WynnClient> = OnceLock::new();
+
+// The free functions share one lazily built client, and with it its connection pool and rate
+// limit state. Pooled connections belong to the runtime that opened them, so a program that runs
+// several runtimes one after another should hold its own `WynnClient` per runtime instead.
+pub(crate) fn default_client() -> Result<&'static WynnClient> {
+    if let Some(client) = DEFAULT_CLIENT.get() {
+        return Ok(client);
+    }
+
+    // Two callers racing here both build a client, only the first one to finish is kept.
+    let client = WynnClient::builder().build()?;
+    Ok(DEFAULT_CLIENT.get_or_init(|| client))
+}
@@ -0,0 +1,91 @@
+use std::collections::BTreeMap;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+
+use crate::error::{Result, WynnError};
+
+// Decodes a response body straight into `T`, reporting the JSON path of the value that failed.
+pub(crate) fn decode<T: DeserializeOwned>(body: &[u8]) -> Result<T> {
+    let deserializer = &mut serde_json::Deserializer::from_slice(body);
+
+    serde_path_to_error::deserialize(deserializer).map_err(|e| {
+        let path = e.path().to_string();
+        let path = if path == "." { String::new() } else { path };
+        WynnError::decode(path, e.into_inner().to_string())
+    })
+}
+
+// Many responses are objects keyed by an id (a UUID, a class id, a territory name, ...) that
+// belongs on the value itself.
+pub(crate) trait Keyed {
+    fn set_key(&mut self, key: String);
+}
+
+pub(crate) fn into_keyed_vec<T: Keyed>(map: BTreeMap<String, T>) -> Vec<T> {
+    map.into_iter()
+        .map(|(key, mut value)| {
+            value.set_key(key);
+            value
+        })
+        .collect()
+}
+
+pub(crate) fn keyed_vec<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Keyed,
+{
+    Ok(into_keyed_vec(BTreeMap::deserialize(deserializer)?))
+}
+
+// The API sends `null` for a lot of values it otherwise always includes.
+pub(crate) fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Ok(Option::deserialize(deserializer)?.unwrap_or_default())
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StringOrNumber {
+    Number(i64),
+    Float(f64),
+    String(String),
+}
+
+// Marker coordinates have been sent both as numbers and as numeric strings.
+pub(crate) fn string_or_number<'de, D>(deserializer: D) -> Result<i32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<StringOrNumber>::deserialize(deserializer)? {
+        Some(value) => coordinate_from(value),
+        None => Ok(0),
+    }
+}
+
+// Like `string_or_number` but `null` is an error, for models where a made up 0 would be wrong.
+pub(crate) fn required_string_or_number<'de, D>(deserializer: D) -> Result<i32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    coordinate_from(StringOrNumber::deserialize(deserializer)?)
+}
+
+fn coordinate_from<E: serde::de::Error>(value: StringOrNumber) -> Result<i32, E> {
+    match value {
+        StringOrNumber::Number(number) => i32::try_from(number).map_err(E::custom),
+        StringOrNumber::Float(number)
+            if number.fract() == 0.0 && number.abs() <= i32::MAX as f64 =>
+        {
+            Ok(number as i32)
+        }
+        StringOrNumber::Float(number) => {
+            Err(E::custom(format!("{} is not a valid coordinate", number)))
+        }
+        StringOrNumber::String(string) => string.trim().parse::<i32>().map_err(E::custom),
+    }
+}
@@ -0,0 +1,153 @@
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use bytes::Bytes;
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+use crate::cache::CacheConfig;
+
+#[derive(Debug, Clone)]
+pub struct StoredResponse {
+    pub url: String,
+    pub fetched_at: SystemTime,
+    pub expires_at: SystemTime,
+    pub body: Bytes,
+}
+
+impl StoredResponse {
+    pub fn is_fresh(&self) -> bool {
+        self.expires_at > SystemTime::now()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DiskEntry<'a> {
+    #[serde(borrow)]
+    url: Cow<'a, str>,
+    fetched_at: u64,
+    expires_at: u64,
+    #[serde(borrow)]
+    body: Cow<'a, str>,
+}
+
+// Keeps one JSON file per URL in a directory so responses survive a restart. Every response is
+// written, including ones the cache headers mark as uncacheable, with an expiry equal to the
+// fetch time; those are never served online but are still available in offline mode.
+#[derive(Debug)]
+pub(crate) struct DiskCache {
+    directory: PathBuf,
+    config: CacheConfig,
+}
+
+impl DiskCache {
+    pub(crate) fn new(directory: PathBuf, config: CacheConfig) -> Self {
+        DiskCache { directory, config }
+    }
+
+    fn entry_path(&self, url: &str) -> PathBuf {
+        self.directory.join(format!("{:016x}.json", fnv1a(url)))
+    }
+
+    pub(crate) async fn get(&self, url: &str) -> Option<StoredResponse> {
+        let contents = tokio::fs::read(self.entry_path(url)).await.ok()?;
+        let entry: DiskEntry = serde_json::from_slice(&contents).ok()?;
+
+        // Different URLs can share a file name if their hashes collide.
+        if entry.url != url {
+            return None;
+        }
+
+        Some(StoredResponse {
+            url: url.to_string(),
+            fetched_at: UNIX_EPOCH + Duration::from_millis(entry.fetched_at),
+            expires_at: UNIX_EPOCH + Duration::from_millis(entry.expires_at),
+            body: Bytes::from(entry.body.into_owned()),
+        })
+    }
+
+    // Failing to write is not an error for the request, the response was still fetched.
+    pub(crate) async fn insert(&self, url: &str, path: &str, headers: &HeaderMap, body: &Bytes) {
+        let Ok(body) = std::str::from_utf8(body) else {
+            return;
+        };
+
+        let fetched_at = SystemTime::now();
+        let expires_at = fetched_at
+            .checked_add(self.config.ttl_for(path, headers).unwrap_or_default())
+            .unwrap_or(fetched_at);
+        let Ok(entry) = serde_json::to_vec(&DiskEntry {
+            url: url.into(),
+            fetched_at: unix_millis(fetched_at),
+            expires_at: unix_millis(expires_at),
+            body: body.into(),
+        }) else {
+            return;
+        };
+
+        if tokio::fs::create_dir_all(&self.directory).await.is_err() {
+            return;
+        }
+
+        // Write to a temporary file first so a crash never leaves a half written entry behind.
+        let entry_path = self.entry_path(url);
+        let temp_path = entry_path.with_extension(format!("{}.tmp", fastrand::u32(..)));
+        if tokio::fs::write(&temp_path, entry).await.is_ok()
+            && tokio::fs::rename(&temp_path, &entry_path).await.is_err()
+        {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+        }
+    }
+
+    // File names are hashes, so finding every entry under a prefix means reading them all.
+    pub(crate) async fn invalidate(&self, url_prefix: &str) -> std::io::Result<()> {
+        let mut entries = match tokio::fs::read_dir(&self.directory).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        };
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            // The directory may be shared, so only files named like `entry_path` are touched.
+            if !path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(is_entry_file_name)
+            {
+                continue;
+            }
+
+            let matches = match tokio::fs::read(&path).await {
+                Ok(contents) => serde_json::from_slice::<DiskEntry>(&contents)
+                    .is_ok_and(|entry| entry.url.starts_with(url_prefix)),
+                Err(_) => false,
+            };
+            if matches {
+                tokio::fs::remove_file(&path).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_entry_file_name(name: &str) -> bool {
+    name.strip_suffix(".json")
+        .is_some_and(|hash| hash.len() == 16 && hash.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+// File names have to stay stable across Rust versions, which `DefaultHasher` does not promise.
+fn fnv1a(value: &str) -> u64 {
+    value.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
@@ -0,0 +1,87 @@
+use reqwest::Url;
+
+use crate::error::{Result, WynnError};
+
+/// A path and query below the base URL with every segment and query value percent-encoded.
+///
+/// Names are passed through as given, so a guild called `A/B #1` stays a single segment.
+#[derive(Debug, Clone)]
+pub(crate) struct Endpoint {
+    // Only the path and query are used, the host is a placeholder.
+    url: Url,
+    invalid_segment: Option<String>,
+}
+
+impl Endpoint {
+    pub(crate) fn new<I>(segments: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        Endpoint::with_segments(segments, true)
+    }
+
+    fn with_segments<I>(segments: I, reject_empty: bool) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut url = Url::parse("http://localhost/").expect("the placeholder URL is valid");
+        let mut invalid_segment = None;
+        if let Ok(mut path) = url.path_segments_mut() {
+            path.clear();
+            for segment in segments {
+                let segment = segment.as_ref();
+                if segment == "." || segment == ".." || (reject_empty && segment.is_empty()) {
+                    invalid_segment.get_or_insert_with(|| segment.to_string());
+                }
+                path.push(segment);
+            }
+        }
+
+        Endpoint {
+            url,
+            invalid_segment,
+        }
+    }
+
+    /// Reads a plain path such as `/guild/Salt Mines?identifier=uuid`, written the way the names
+    /// are passed to the endpoint methods, and encodes it the same way they do.
+    pub(crate) fn from_path(path: &str) -> Self {
+        let (path, query) = path.split_once('?').unwrap_or((path, ""));
+        let path = path.strip_prefix('/').unwrap_or(path);
+        // Empty segments are kept so prefixes such as `/` and `/guild/` still work.
+        let endpoint = Endpoint::with_segments(path.split('/'), false);
+
+        query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .fold(endpoint, |endpoint, pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                endpoint.query(key, value)
+            })
+    }
+
+    pub(crate) fn query(mut self, key: &str, value: impl std::fmt::Display) -> Self {
+        self.url
+            .query_pairs_mut()
+            .append_pair(key, &value.to_string());
+        self
+    }
+
+    // `.` and `..` are resolved away by every URL parser, even when encoded, and an empty segment
+    // usually names the parent listing, so any of them would silently request a different endpoint.
+    pub(crate) fn path(&self) -> Result<String> {
+        if let Some(segment) = &self.invalid_segment {
+            return Err(WynnError::InvalidIdentifier {
+                kind: "path segment".to_string(),
+                value: segment.clone(),
+            });
+        }
+
+        Ok(match self.url.query() {
+            Some(query) => format!("{}?{}", self.url.path(), query),
+            None => self.url.path().to_string(),
+        })
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::guild::GuildChoice;
+use crate::player::PlayerChoice;
+
+pub type Result<T, E = WynnError> = std::result::Result<T, E>;
+
+#[derive(Debug, Error)]
+pub enum WynnError {
+    #[error("the requested resource was not found")]
+    NotFound,
+    #[error("rate limited by the API{}", retry_after.map(|d| format!(", retry after {}s", d.as_secs())).unwrap_or_default())]
+    RateLimited { retry_after: Option<Duration> },
+    #[error("the identifier matched {} results", .0.len())]
+    MultipleChoices(MultipleChoices),
+    #[error("the API rejected the request with status {status}{}", message.as_ref().map(|m| format!(": {}", m)).unwrap_or_default())]
+    ClientError {
+        status: u16,
+        message: Option<String>,
+    },
+    #[error("the API rejected the credentials with status {status}{}", message.as_ref().map(|m| format!(": {}", m)).unwrap_or_default())]
+    Unauthorized {
+        status: u16,
+        message: Option<String>,
+    },
+    #[error("the API failed with status {status}")]
+    ServerError { status: u16 },
+    #[error("unexpected status {status} from the API")]
+    UnexpectedStatus { status: u16 },
+    #[error("failed to decode {}: {message}", if path.is_empty() { "the response" } else { path })]
+    Decode {
+        field: String,
+        path: String,
+        message: String,
+    },
+    #[error("failed to make the API request: {0}")]
+    Transport(#[from] reqwest::Error),
+    #[error("invalid client configuration: {0}")]
+    Config(String),
+    #[error("`{value}` is not a valid {kind}")]
+    InvalidIdentifier { kind: String, value: String },
+    #[error("unknown {filter} `{value}` in the item query")]
+    InvalidQuery { filter: String, value: String },
+    #[error("`{board}` is a guild leaderboard, fetch it with `get_guild_leaderboard`")]
+    GuildLeaderboard { board: String },
+    #[error("`{board}` is a player leaderboard, fetch it with `get_leaderboard`")]
+    PlayerLeaderboard { board: String },
+    #[error("no stored response for {url} while offline")]
+    Offline { url: String },
+    #[error("failed to access the response store: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl WynnError {
+    // `path` is the dotted JSON path to the value that failed, empty for the document root.
+    pub(crate) fn decode(path: impl Into<String>, message: impl Into<String>) -> Self {
+        let path = path.into();
+        let field = path.rsplit('.').next().unwrap_or_default().to_string();
+
+        WynnError::Decode {
+            field,
+            path,
+            message: message.into(),
+        }
+    }
+
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            WynnError::NotFound => Some(404),
+            WynnError::RateLimited { .. } => Some(429),
+            WynnError::MultipleChoices(_) => Some(300),
+            WynnError::ClientError { status, .. }
+            | WynnError::Unauthorized { status, .. }
+            | WynnError::ServerError { status }
+            | WynnError::UnexpectedStatus { status } => Some(*status),
+            WynnError::Transport(error) => error.status().map(|status| status.as_u16()),
+            WynnError::Decode { .. }
+            | WynnError::Config(_)
+            | WynnError::InvalidIdentifier { .. }
+            | WynnError::InvalidQuery { .. }
+            | WynnError::GuildLeaderboard { .. }
+            | WynnError::PlayerLeaderboard { .. }
+            | WynnError::Offline { .. }
+            | WynnError::Io(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "BTreeMap<String, RawChoice>")]
+pub enum MultipleChoices {
+    Players(Vec<PlayerChoice>),
+    Guilds(Vec<GuildChoice>),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawChoice {
+    stored_name: Option<String>,
+    name: Option<String>,
+    rank: Option<String>,
+    prefix: Option<String>,
+}
+
+impl TryFrom<BTreeMap<String, RawChoice>> for MultipleChoices {
+    type Error = String;
+
+    fn try_from(choices: BTreeMap<String, RawChoice>) -> Result<Self, Self::Error> {
+        // Player candidates carry a `storedName`, guild candidates a `name` and `prefix`.
+        let players = choices.values().any(|choice| choice.stored_name.is_some());
+
+        if players {
+            choices
+                .into_iter()
+                .map(|(uuid, choice)| {
+                    Ok(PlayerChoice {
+                        stored_name: choice
+                            .stored_name
+                            .ok_or_else(|| format!("{}: missing field `storedName`", uuid))?,
+                        uuid,
+                        rank: choice.rank,
+                        prefix: choice.prefix,
+                    })
+                })
+                .collect::<Result<_, String>>()
+                .map(MultipleChoices::Players)
+        } else {
+            choices
+                .into_iter()
+                .map(|(uuid, choice)| {
+                    Ok(GuildChoice {
+                        name: choice
+                            .name
+                            .ok_or_else(|| format!("{}: missing field `name`", uuid))?,
+                        prefix: choice
+                            .prefix
+                            .ok_or_else(|| format!("{}: missing field `prefix`", uuid))?,
+                        uuid,
+                    })
+                })
+                .collect::<Result<_, String>>()
+                .map(MultipleChoices::Guilds)
+        }
+    }
+}
+
+impl MultipleChoices {
+    pub fn len(&self) -> usize {
+        match self {
+            MultipleChoices::Players(players) => players.len(),
+            MultipleChoices::Guilds(guilds) => guilds.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
@@ -1,8 +1,19 @@
-use crate::{json_to_location, Location};
-use anyhow::Context;
-use serde_json::Value;
-
-#[derive(Debug)]
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use crate::client::{default_client, WynnClient};
+use crate::decode::{into_keyed_vec, null_as_default, Keyed};
+use crate::endpoint::Endpoint;
+use crate::error::Result;
+use crate::identifier::{GuildIdentifier, GuildIdentifierKind};
+use crate::timestamp::Timestamp;
+use crate::Location;
+use serde::de::{IgnoredAny, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GuildData {
     pub uuid: String,
     pub name: String,
@@ -11,10 +22,13 @@ pub struct GuildData {
     pub xp_percent: i32,
     pub territories: i32,
     pub wars: i32,
-    pub created: String,
+    pub created: Timestamp,
+    #[serde(deserialize_with = "guild_members")]
     pub members: Vec<GuildMember>,
     pub online: i32,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub banner: GuildBanner,
+    #[serde(deserialize_with = "season_ranks")]
     pub season_ranks: Vec<SeasonRank>,
 }
 
@@ -27,7 +41,7 @@ pub struct GuildMember {
     pub rank: String,
     pub contributed: i64,
     pub contribution_rank: i32,
-    pub joined: String,
+    pub joined: Option<Timestamp>,
 }
 
 #[derive(Debug)]
@@ -37,7 +51,8 @@ pub struct SeasonRank {
     pub final_territories: i32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GuildBanner {
     pub base: String,
     pub tier: i32,
@@ -45,13 +60,15 @@ pub struct GuildBanner {
     pub layers: Vec<BannerLayer>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct BannerLayer {
     pub colour: String,
     pub pattern: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct SimpleGuildData {
     pub uuid: String,
     pub name: String,
@@ -59,398 +76,230 @@ pub struct SimpleGuildData {
 }
 
 #[derive(Debug)]
+pub struct GuildChoice {
+    pub uuid: String,
+    pub name: String,
+    pub prefix: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(from = "RawTerritory")]
 pub struct TerritoryData {
     pub territory_name: String,
     pub owner: SimpleGuildData,
-    pub acquired: String,
+    pub acquired: Option<Timestamp>,
     pub start_location: Location,
     pub end_location: Location,
 }
 
-async fn fetch_guilds(uuid: bool) -> anyhow::Result<Vec<String>> {
-    let identifier = if uuid { "uuid" } else { "name" };
-    let url = format!(
-        "https://api.wynncraft.com/v3/guild/list/guild?identifier={}",
-        identifier
-    );
-
-    let response = reqwest::get(&url)
-        .await
-        .context("Failed to make the API request")?;
-
-    let data: Value = response
-        .json()
-        .await
-        .context("Failed to parse the JSON response")?;
+impl TerritoryData {
+    /// How long the current owner has held the territory, `None` when the API sent no date.
+    pub fn held_for(&self) -> Option<Duration> {
+        self.acquired.as_ref()?.elapsed()
+    }
+}
 
-    let guild_data = data
-        .as_object()
-        .ok_or_else(|| anyhow::Error::msg("Expected data to be a JSON object"))?;
+impl Keyed for SimpleGuildData {
+    fn set_key(&mut self, key: String) {
+        self.uuid = key;
+    }
+}
 
-    let guilds: Vec<String> = guild_data.keys().cloned().collect();
+impl Keyed for TerritoryData {
+    fn set_key(&mut self, key: String) {
+        self.territory_name = key;
+    }
+}
 
-    Ok(guilds)
+#[derive(Deserialize)]
+struct RawTerritory {
+    guild: SimpleGuildData,
+    #[serde(default)]
+    acquired: Option<Timestamp>,
+    location: RawTerritoryLocation,
 }
 
-pub async fn get_guilds(uuid: bool) -> anyhow::Result<Vec<String>> {
-    fetch_guilds(uuid).await
+#[derive(Deserialize)]
+struct RawTerritoryLocation {
+    start: Location,
+    end: Location,
 }
 
-pub async fn get_guilds_data() -> anyhow::Result<Vec<SimpleGuildData>> {
-    let url = "https://api.wynncraft.com/v3/guild/list/guild?identifier=uuid";
-
-    let response = reqwest::get(url)
-        .await
-        .context("Failed to make the API request")?;
-    let data: Value = response
-        .json()
-        .await
-        .context("Failed to parse the JSON response")?;
-
-    let guilds_object = data
-        .as_object()
-        .ok_or_else(|| anyhow::Error::msg("Expected the JSON to be an object"))?;
-
-    let mut guilds_data = Vec::new();
-    for (uuid, details) in guilds_object {
-        if let Some(guild_details) = details.as_object() {
-            let name = guild_details
-                .get("name")
-                .and_then(Value::as_str)
-                .unwrap_or_default()
-                .to_string();
-            let prefix = guild_details
-                .get("prefix")
-                .and_then(Value::as_str)
-                .unwrap_or_default()
-                .to_string();
-            guilds_data.push(SimpleGuildData {
-                uuid: uuid.clone(),
-                name,
-                prefix,
-            });
+impl From<RawTerritory> for TerritoryData {
+    fn from(raw: RawTerritory) -> Self {
+        TerritoryData {
+            territory_name: String::new(),
+            owner: raw.guild,
+            acquired: raw.acquired,
+            start_location: raw.location.start,
+            end_location: raw.location.end,
         }
     }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct RawGuildMember {
+    username: Option<String>,
+    uuid: Option<String>,
+    online: bool,
+    server: Option<String>,
+    contributed: i64,
+    contribution_rank: i32,
+    joined: Option<Timestamp>,
+}
 
-    Ok(guilds_data)
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSeasonRank {
+    rating: i32,
+    final_territories: i32,
 }
 
-fn fetch_guild(data: Value, uuid: bool) -> anyhow::Result<GuildData> {
-    let guild_object = data
-        .as_object()
-        .ok_or_else(|| anyhow::Error::msg("Expected the JSON to be an object"))?;
-
-    let guild_uuid = guild_object["uuid"]
-        .as_str()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'uuid' to be a String"))?
-        .parse::<String>()
-        .unwrap();
-
-    let name = guild_object["name"]
-        .as_str()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'name' to be a String"))?
-        .parse::<String>()
-        .unwrap();
-
-    let prefix = guild_object["prefix"]
-        .as_str()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'prefix' to be a String"))?
-        .parse::<String>()
-        .unwrap();
-
-    let level = guild_object["level"]
-        .as_i64()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'level' to be an integer"))?
-        as i32;
-
-    let xp_percent = guild_object["xpPercent"]
-        .as_i64()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'xpPercent' to be an integer"))?
-        as i32;
-
-    let territories = guild_object["territories"]
-        .as_i64()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'territories' to be an integer"))?
-        as i32;
-
-    let wars = guild_object["wars"]
-        .as_i64()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'wars' to be an integer"))?
-        as i32;
-
-    let created = guild_object["created"]
-        .as_str()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'created' to be a String"))?
-        .parse::<String>()
-        .unwrap();
-
-    let online = guild_object["online"]
-        .as_i64()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'online' to be an integer"))?
-        as i32;
-
-    let mut members = Vec::new();
-
-    let members_object = guild_object
-        .get("members")
-        .ok_or_else(|| anyhow::Error::msg("Expected 'members' to be a JSON object"))?
-        .as_object()
-        .unwrap();
-
-    for (rank, member_details) in members_object {
-        if rank == "total" {
-            continue;
+// Members are grouped by rank next to a `total` count, and keyed by whichever identifier the
+// request asked for. The other identifier is included in the member itself.
+fn guild_members<'de, D>(deserializer: D) -> Result<Vec<GuildMember>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct MembersVisitor;
+
+    impl<'de> Visitor<'de> for MembersVisitor {
+        type Value = Vec<GuildMember>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("guild members grouped by rank")
         }
 
-        for (member_identifier, details) in member_details.as_object().unwrap() {
-            let member_uuid;
-            let username;
-
-            if uuid {
-                member_uuid = member_identifier.to_string();
-                username = details
-                    .get("username")
-                    .and_then(Value::as_str)
-                    .unwrap_or_default()
-                    .to_string();
-            } else {
-                member_uuid = details
-                    .get("uuid")
-                    .and_then(Value::as_str)
-                    .unwrap_or_default()
-                    .to_string();
-                username = member_identifier.to_string();
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut members = Vec::new();
+            while let Some(rank) = map.next_key::<String>()? {
+                if rank == "total" {
+                    map.next_value::<IgnoredAny>()?;
+                    continue;
+                }
+
+                let rank_members: BTreeMap<String, RawGuildMember> = map.next_value()?;
+                for (identifier, member) in rank_members {
+                    let (username, uuid) = match member.uuid {
+                        Some(uuid) => (identifier, uuid),
+                        None => (member.username.unwrap_or_default(), identifier),
+                    };
+
+                    members.push(GuildMember {
+                        username,
+                        uuid,
+                        online: member.online,
+                        server: member.server.unwrap_or_default(),
+                        rank: rank.clone(),
+                        contributed: member.contributed,
+                        contribution_rank: member.contribution_rank,
+                        joined: member.joined,
+                    });
+                }
             }
 
-            let online = details
-                .get("online")
-                .and_then(Value::as_bool)
-                .unwrap_or_default();
-            let server = details
-                .get("server")
-                .and_then(Value::as_str)
-                .unwrap_or_default()
-                .to_string();
-            let contributed = details
-                .get("contributed")
-                .and_then(Value::as_i64)
-                .unwrap_or_default();
-            let contribution_rank = details
-                .get("contributionRank")
-                .and_then(Value::as_i64)
-                .unwrap_or_default() as i32;
-            let joined = details
-                .get("joined")
-                .and_then(Value::as_str)
-                .unwrap_or_default()
-                .to_string();
-
-            members.push(GuildMember {
-                username: username.to_string(),
-                uuid: member_uuid,
-                online,
-                server,
-                rank: rank.to_string(),
-                contributed,
-                contribution_rank,
-                joined,
-            });
+            Ok(members)
         }
     }
 
-    let banner_object = guild_object
-        .get("banner")
-        .ok_or_else(|| anyhow::Error::msg("Expected 'banner' to be a JSON object"))?
-        .as_object()
-        .unwrap();
-
-    let base = banner_object["base"]
-        .as_str()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'base' to be a String"))?
-        .parse::<String>()
-        .unwrap();
-
-    let tier = banner_object["tier"]
-        .as_i64()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'tier' to be an integer"))?
-        as i32;
-
-    let structure = banner_object["structure"]
-        .as_str()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'structure' to be a String"))?
-        .parse::<String>()
-        .unwrap();
-
-    let layers_array = banner_object["layers"]
-        .as_array()
-        .expect("Expected 'layers' to be a JSON array");
-    let mut layers = Vec::new();
-
-    for layer_value in layers_array {
-        let colour = layer_value["colour"]
-            .as_str()
-            .unwrap_or_default()
-            .to_string();
-        let pattern = layer_value["pattern"]
-            .as_str()
-            .unwrap_or_default()
-            .to_string();
-
-        layers.push(BannerLayer { colour, pattern });
+    deserializer.deserialize_map(MembersVisitor)
+}
+
+fn season_ranks<'de, D>(deserializer: D) -> Result<Vec<SeasonRank>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let season_ranks: BTreeMap<i32, RawSeasonRank> = BTreeMap::deserialize(deserializer)?;
+
+    Ok(season_ranks
+        .into_iter()
+        .map(|(season, rank)| SeasonRank {
+            season,
+            rating: rank.rating,
+            final_territories: rank.final_territories,
+        })
+        .collect())
+}
+
+impl WynnClient {
+    pub async fn get_guilds(&self, uuid: bool) -> Result<Vec<String>> {
+        let identifier = if uuid { "uuid" } else { "name" };
+        let guilds: BTreeMap<String, IgnoredAny> = self
+            .get(Endpoint::new(["guild", "list", "guild"]).query("identifier", identifier))
+            .await?;
+
+        Ok(guilds.into_keys().collect())
+    }
+
+    pub async fn get_guilds_data(&self) -> Result<Vec<SimpleGuildData>> {
+        let guilds = self
+            .get(Endpoint::new(["guild", "list", "guild"]).query("identifier", "uuid"))
+            .await?;
+
+        Ok(into_keyed_vec(guilds))
+    }
+
+    // Members are keyed by UUID, the decoded members carry both the username and UUID anyway.
+    pub async fn get_guild(&self, identifier: &GuildIdentifier) -> Result<GuildData> {
+        let value = identifier.as_str();
+        let endpoint = match identifier.kind() {
+            GuildIdentifierKind::Name => Endpoint::new(["guild", value]),
+            GuildIdentifierKind::Prefix => Endpoint::new(["guild", "prefix", value]),
+            GuildIdentifierKind::Uuid => Endpoint::new(["guild", "uuid", value]),
+        };
+
+        self.get(endpoint.query("identifier", "uuid")).await
     }
 
-    let banner = GuildBanner {
-        base,
-        tier,
-        structure,
-        layers,
-    };
-
-    let season_ranks_object = guild_object["seasonRanks"]
-        .as_object()
-        .expect("Expected 'seasonRanks' to be a JSON object");
-
-    let mut season_ranks = Vec::new();
-
-    for (season, details) in season_ranks_object {
-        let season_number = season
-            .parse::<i32>()
-            .expect("Expected season number to be an integer");
-        let rating = details["rating"]
-            .as_i64()
-            .expect("Expected 'rating' to be an integer") as i32;
-        let final_territories = details["finalTerritories"]
-            .as_i64()
-            .expect("Expected 'finalTerritories' to be an integer")
-            as i32;
-
-        season_ranks.push(SeasonRank {
-            season: season_number,
-            rating,
-            final_territories,
-        });
+    pub async fn get_guild_from_name(&self, name: &str) -> Result<GuildData> {
+        self.get_guild(&GuildIdentifier::name(name)?).await
     }
 
-    Ok(GuildData {
-        uuid: guild_uuid,
-        name,
-        prefix,
-        level,
-        xp_percent,
-        territories,
-        wars,
-        created,
-        members,
-        online,
-        banner,
-        season_ranks,
-    })
+    pub async fn get_guild_from_prefix(&self, prefix: &str) -> Result<GuildData> {
+        self.get_guild(&GuildIdentifier::prefix(prefix)?).await
+    }
+
+    pub async fn resolve_guild_choice(&self, choice: &GuildChoice) -> Result<GuildData> {
+        self.get_guild(&GuildIdentifier::uuid(&choice.uuid)?).await
+    }
+
+    pub async fn get_territory_data(&self) -> Result<Vec<TerritoryData>> {
+        let territories = self
+            .get(Endpoint::new(["guild", "list", "territory"]))
+            .await?;
+
+        Ok(into_keyed_vec(territories))
+    }
 }
 
-async fn fetch_guild_name(name: &str, uuid: bool) -> anyhow::Result<GuildData> {
-    let identifier = if uuid { "uuid" } else { "username" };
-    let url = format!(
-        "https://api.wynncraft.com/v3/guild/{}?identifier={}",
-        name, identifier
-    );
-
-    let response = reqwest::get(url)
-        .await
-        .context("Failed to make the API request")?;
-    let data: Value = response
-        .json()
-        .await
-        .context("Failed to parse the JSON response")?;
-
-    fetch_guild(data, uuid)
+pub async fn get_guilds(uuid: bool) -> Result<Vec<String>> {
+    default_client()?.get_guilds(uuid).await
 }
 
-pub async fn get_guild_from_name(name: &str, uuid: bool) -> anyhow::Result<GuildData> {
-    fetch_guild_name(name, uuid).await
+pub async fn get_guilds_data() -> Result<Vec<SimpleGuildData>> {
+    default_client()?.get_guilds_data().await
 }
 
-async fn fetch_guild_prefix(prefix: &str, uuid: bool) -> anyhow::Result<GuildData> {
-    let identifier = if uuid { "uuid" } else { "username" };
-    let url = format!(
-        "https://api.wynncraft.com/v3/guild/prefix/{}?identifier={}",
-        prefix, identifier
-    );
-
-    let response = reqwest::get(url)
-        .await
-        .context("Failed to make the API request")?;
-    let data: Value = response
-        .json()
-        .await
-        .context("Failed to parse the JSON response")?;
-
-    fetch_guild(data, uuid)
+pub async fn get_guild(identifier: &GuildIdentifier) -> Result<GuildData> {
+    default_client()?.get_guild(identifier).await
 }
 
-pub async fn get_guild_from_prefix(prefix: &str, uuid: bool) -> anyhow::Result<GuildData> {
-    fetch_guild_prefix(prefix, uuid).await
+pub async fn get_guild_from_name(name: &str) -> Result<GuildData> {
+    default_client()?.get_guild_from_name(name).await
 }
 
-pub async fn get_territory_data() -> anyhow::Result<Vec<TerritoryData>> {
-    let url = "https://api.wynncraft.com/v3/guild/list/territory";
-
-    let response = reqwest::get(url)
-        .await
-        .context("Failed to make the API request")?;
-    let data: Value = response
-        .json()
-        .await
-        .context("Failed to parse the JSON response")?;
-
-    let territories_object = data
-        .as_object()
-        .ok_or_else(|| anyhow::Error::msg("Expected the JSON to be an object"))?;
-
-    let mut territory_data = Vec::new();
-    for (territory_name, details) in territories_object {
-        if let Some(territory_details) = details.as_object() {
-            let owner = territory_details
-                .get("guild")
-                .and_then(Value::as_object)
-                .ok_or_else(|| anyhow::Error::msg("Expected 'guild' to be a JSON object"))?;
-            let simple_guild_data = SimpleGuildData {
-                uuid: owner
-                    .get("uuid")
-                    .and_then(Value::as_str)
-                    .unwrap_or_default()
-                    .to_string(),
-                name: owner
-                    .get("name")
-                    .and_then(Value::as_str)
-                    .unwrap_or_default()
-                    .to_string(),
-                prefix: owner
-                    .get("prefix")
-                    .and_then(Value::as_str)
-                    .unwrap_or_default()
-                    .to_string(),
-            };
-            let acquired = territory_details
-                .get("acquired")
-                .and_then(Value::as_str)
-                .unwrap_or_default()
-                .to_string();
-            let location = territory_details
-                .get("location")
-                .and_then(Value::as_object)
-                .ok_or_else(|| anyhow::Error::msg("Expected 'location' to be a JSON object"))?;
-            let start_location = json_to_location(location.get("start").unwrap());
-            let end_location = json_to_location(location.get("end").unwrap());
-
-            territory_data.push(TerritoryData {
-                territory_name: territory_name.clone(),
-                owner: simple_guild_data,
-                acquired,
-                start_location,
-                end_location,
-            });
-        }
-    }
+pub async fn get_guild_from_prefix(prefix: &str) -> Result<GuildData> {
+    default_client()?.get_guild_from_prefix(prefix).await
+}
+
+pub async fn resolve_guild_choice(choice: &GuildChoice) -> Result<GuildData> {
+    default_client()?.resolve_guild_choice(choice).await
+}
 
-    Ok(territory_data)
+pub async fn get_territory_data() -> Result<Vec<TerritoryData>> {
+    default_client()?.get_territory_data().await
 }
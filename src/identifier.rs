@@ -0,0 +1,217 @@
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::{Result, WynnError};
+
+/// A player looked up by username or UUID, UUIDs are kept in lowercase dashed form.
+///
+/// Only the constructors below can build one, so every identifier has been validated.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlayerIdentifier {
+    kind: PlayerIdentifierKind,
+    value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PlayerIdentifierKind {
+    Username,
+    Uuid,
+}
+
+/// A guild looked up by its name, its prefix or its UUID, built through the validating
+/// constructors only.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GuildIdentifier {
+    kind: GuildIdentifierKind,
+    value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GuildIdentifierKind {
+    Name,
+    Prefix,
+    Uuid,
+}
+
+impl PlayerIdentifier {
+    /// Reads a UUID, dashed or not, and falls back to a username for anything else.
+    pub fn parse(identifier: &str) -> Result<Self> {
+        match normalise_uuid(identifier) {
+            Some(uuid) => Ok(PlayerIdentifier {
+                kind: PlayerIdentifierKind::Uuid,
+                value: uuid,
+            }),
+            None => PlayerIdentifier::username(identifier),
+        }
+    }
+
+    // Usernames are 3 to 16 letters, digits and underscores.
+    pub fn username(username: &str) -> Result<Self> {
+        let valid = (3..=16).contains(&username.len())
+            && username
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+        if valid {
+            Ok(PlayerIdentifier {
+                kind: PlayerIdentifierKind::Username,
+                value: username.to_string(),
+            })
+        } else {
+            Err(invalid("username", username))
+        }
+    }
+
+    pub fn uuid(uuid: &str) -> Result<Self> {
+        normalise_uuid(uuid)
+            .map(|value| PlayerIdentifier {
+                kind: PlayerIdentifierKind::Uuid,
+                value,
+            })
+            .ok_or_else(|| invalid("UUID", uuid))
+    }
+
+    pub fn kind(&self) -> PlayerIdentifierKind {
+        self.kind
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+impl GuildIdentifier {
+    pub fn name(name: &str) -> Result<Self> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(invalid("guild name", name));
+        }
+
+        Ok(GuildIdentifier {
+            kind: GuildIdentifierKind::Name,
+            value: name.to_string(),
+        })
+    }
+
+    pub fn prefix(prefix: &str) -> Result<Self> {
+        let valid =
+            (1..=4).contains(&prefix.len()) && prefix.chars().all(|c| c.is_ascii_alphabetic());
+
+        if valid {
+            Ok(GuildIdentifier {
+                kind: GuildIdentifierKind::Prefix,
+                value: prefix.to_string(),
+            })
+        } else {
+            Err(invalid("guild prefix", prefix))
+        }
+    }
+
+    pub fn uuid(uuid: &str) -> Result<Self> {
+        normalise_uuid(uuid)
+            .map(|value| GuildIdentifier {
+                kind: GuildIdentifierKind::Uuid,
+                value,
+            })
+            .ok_or_else(|| invalid("UUID", uuid))
+    }
+
+    pub fn kind(&self) -> GuildIdentifierKind {
+        self.kind
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+}
+
+fn invalid(kind: &str, value: &str) -> WynnError {
+    WynnError::InvalidIdentifier {
+        kind: kind.to_string(),
+        value: value.to_string(),
+    }
+}
+
+// Accepts 32 hex digits with or without the usual dashes, in any case.
+fn normalise_uuid(uuid: &str) -> Option<String> {
+    let dashed = uuid.len() == 36
+        && uuid
+            .char_indices()
+            .all(|(i, c)| matches!(i, 8 | 13 | 18 | 23) == (c == '-'));
+    let hex: String = if dashed {
+        uuid.chars().filter(|c| *c != '-').collect()
+    } else {
+        uuid.to_string()
+    };
+    if hex.len() != 32 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let hex = hex.to_ascii_lowercase();
+    Some(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    ))
+}
+
+impl fmt::Display for PlayerIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Display for GuildIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PlayerIdentifier {
+    type Err = WynnError;
+
+    fn from_str(identifier: &str) -> Result<Self> {
+        PlayerIdentifier::parse(identifier)
+    }
+}
+
+impl TryFrom<&str> for PlayerIdentifier {
+    type Error = WynnError;
+
+    fn try_from(identifier: &str) -> Result<Self> {
+        PlayerIdentifier::parse(identifier)
+    }
+}
+
+impl TryFrom<String> for PlayerIdentifier {
+    type Error = WynnError;
+
+    fn try_from(identifier: String) -> Result<Self> {
+        PlayerIdentifier::parse(&identifier)
+    }
+}
+
+impl TryFrom<&String> for PlayerIdentifier {
+    type Error = WynnError;
+
+    fn try_from(identifier: &String) -> Result<Self> {
+        PlayerIdentifier::parse(identifier)
+    }
+}
+
+impl From<&PlayerIdentifier> for PlayerIdentifier {
+    fn from(identifier: &PlayerIdentifier) -> Self {
+        identifier.clone()
+    }
+}
+
+// Lets identifiers that are already built be passed where a fallible conversion is expected.
+impl From<Infallible> for WynnError {
+    fn from(never: Infallible) -> Self {
+        match never {}
+    }
+}
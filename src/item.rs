@@ -0,0 +1,678 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::client::{default_client, WynnClient};
+use crate::decode::{into_keyed_vec, null_as_default, Keyed};
+use crate::endpoint::Endpoint;
+use crate::error::{Result, WynnError};
+use futures::stream::{self, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+#[serde(from = "RawItem")]
+pub struct Item {
+    pub name: String,
+    pub internal_name: String,
+    pub kind: ItemKind,
+    pub rarity: Option<ItemRarity>,
+    pub requirements: ItemRequirements,
+    pub base: HashMap<String, StatValue>,
+    pub identifications: HashMap<String, StatValue>,
+    pub powder_slots: Option<i32>,
+    pub lore: Option<String>,
+    pub drop_restriction: Option<String>,
+    pub identified: bool,
+}
+
+/// The item's `type` along with the fields that only that type has.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemKind {
+    Weapon {
+        weapon_type: String,
+        attack_speed: Option<String>,
+        average_dps: Option<f32>,
+    },
+    Armour {
+        armour_type: String,
+        armour_material: Option<String>,
+    },
+    Accessory {
+        accessory_type: String,
+    },
+    Tome {
+        tome_type: String,
+    },
+    Charm,
+    Ingredient {
+        tier: i32,
+    },
+    Material {
+        tier: i32,
+    },
+    Tool {
+        tool_type: String,
+        gathering_speed: Option<i32>,
+    },
+    Unknown(String),
+}
+
+impl ItemKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ItemKind::Weapon { .. } => "weapon",
+            ItemKind::Armour { .. } => "armour",
+            ItemKind::Accessory { .. } => "accessory",
+            ItemKind::Tome { .. } => "tome",
+            ItemKind::Charm => "charm",
+            ItemKind::Ingredient { .. } => "ingredient",
+            ItemKind::Material { .. } => "material",
+            ItemKind::Tool { .. } => "tool",
+            ItemKind::Unknown(kind) => kind,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(from = "String")]
+pub enum ItemRarity {
+    Common,
+    Unique,
+    Rare,
+    Legendary,
+    Fabled,
+    Mythic,
+    Set,
+    Unknown(String),
+}
+
+impl ItemRarity {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ItemRarity::Common => "common",
+            ItemRarity::Unique => "unique",
+            ItemRarity::Rare => "rare",
+            ItemRarity::Legendary => "legendary",
+            ItemRarity::Fabled => "fabled",
+            ItemRarity::Mythic => "mythic",
+            ItemRarity::Set => "set",
+            ItemRarity::Unknown(rarity) => rarity,
+        }
+    }
+}
+
+impl From<String> for ItemRarity {
+    fn from(rarity: String) -> Self {
+        match rarity.as_str() {
+            "common" => ItemRarity::Common,
+            "unique" => ItemRarity::Unique,
+            "rare" => ItemRarity::Rare,
+            "legendary" => ItemRarity::Legendary,
+            "fabled" => ItemRarity::Fabled,
+            "mythic" => ItemRarity::Mythic,
+            "set" => ItemRarity::Set,
+            _ => ItemRarity::Unknown(rarity),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ItemRequirements {
+    pub level: Option<i32>,
+    pub class_requirement: Option<String>,
+    pub strength: Option<i32>,
+    pub dexterity: Option<i32>,
+    pub intelligence: Option<i32>,
+    pub defence: Option<i32>,
+    pub agility: Option<i32>,
+    pub quest: Option<String>,
+    // Crafting skills that can use an ingredient.
+    pub skills: Vec<String>,
+}
+
+/// A base stat or identification, either a fixed value or the range it can roll in.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum StatValue {
+    Fixed(i32),
+    Range {
+        min: i32,
+        max: i32,
+        #[serde(default)]
+        raw: i32,
+    },
+}
+
+/// One page of the item database along with where it sits in the full listing.
+#[derive(Debug)]
+pub struct ItemPage {
+    pub items: Vec<Item>,
+    pub count: i32,
+    pub pages: i32,
+    pub current: i32,
+    pub previous: Option<i32>,
+    pub next: Option<i32>,
+}
+
+/// Filters for `search_items`, serialised as the request body.
+///
+/// Every filter can be given more than once, an item has to match one of the values given for
+/// each filter that is set.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemQuery {
+    #[serde(rename = "query", skip_serializing_if = "Vec::is_empty")]
+    pub names: Vec<String>,
+    #[serde(rename = "type", skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<String>,
+    #[serde(rename = "tier", skip_serializing_if = "Vec::is_empty")]
+    pub tiers: Vec<ItemTier>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level_range: Option<(i32, i32)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub class_requirement: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub identifications: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attack_speed: Vec<String>,
+}
+
+/// Gear is filtered by rarity, ingredients and materials by their numeric tier.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ItemTier {
+    Rarity(String),
+    Tier(i32),
+}
+
+impl ItemQuery {
+    pub fn new() -> Self {
+        ItemQuery::default()
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.names.push(name.into());
+        self
+    }
+
+    /// Either an item type such as `weapon` or a subtype such as `wand` or `helmet`.
+    pub fn item_type(mut self, item_type: impl Into<String>) -> Self {
+        self.types.push(item_type.into());
+        self
+    }
+
+    pub fn rarity(mut self, rarity: ItemRarity) -> Self {
+        self.tiers
+            .push(ItemTier::Rarity(rarity.as_str().to_string()));
+        self
+    }
+
+    pub fn tier(mut self, tier: i32) -> Self {
+        self.tiers.push(ItemTier::Tier(tier));
+        self
+    }
+
+    pub fn level_range(mut self, min: i32, max: i32) -> Self {
+        self.level_range = Some((min, max));
+        self
+    }
+
+    pub fn class_requirement(mut self, class: impl Into<String>) -> Self {
+        self.class_requirement = Some(class.into());
+        self
+    }
+
+    pub fn identification(mut self, identification: impl Into<String>) -> Self {
+        self.identifications.push(identification.into());
+        self
+    }
+
+    pub fn attack_speed(mut self, attack_speed: impl Into<String>) -> Self {
+        self.attack_speed.push(attack_speed.into());
+        self
+    }
+}
+
+/// The filters and identifications the item endpoints understand.
+#[derive(Debug, Deserialize)]
+#[serde(from = "RawItemMetadata")]
+pub struct ItemMetadata {
+    pub identifications: Vec<Identification>,
+    pub filters: ItemFilters,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ItemFilters {
+    #[serde(rename = "type")]
+    pub types: Vec<String>,
+    // Subtypes such as `wand` or `helmet`, grouped by the type they belong to.
+    #[serde(rename = "advanced")]
+    pub subtypes: BTreeMap<String, Vec<String>>,
+    #[serde(rename = "tier")]
+    pub tiers: TierFilters,
+    pub attack_speed: Vec<String>,
+    pub professions: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct TierFilters {
+    pub items: Vec<String>,
+    pub ingredients: Vec<i32>,
+    pub materials: Vec<i32>,
+}
+
+/// An identification key along with how it is shown in game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Identification {
+    pub key: String,
+    pub name: String,
+    pub unit: IdentificationUnit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentificationUnit {
+    Percent,
+    Raw,
+    PerThreeSeconds,
+    PerFiveSeconds,
+    Tier,
+}
+
+impl IdentificationUnit {
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            IdentificationUnit::Percent => "%",
+            IdentificationUnit::Raw => "",
+            IdentificationUnit::PerThreeSeconds => "/3s",
+            IdentificationUnit::PerFiveSeconds => "/5s",
+            IdentificationUnit::Tier => " tier",
+        }
+    }
+}
+
+impl Identification {
+    /// Works out the display name and unit from the key alone, for example `rawMainAttackDamage`
+    /// becomes "Main Attack Damage" with a raw unit.
+    pub fn from_key(key: &str) -> Self {
+        let unit = match key {
+            "rawAttackSpeed" => IdentificationUnit::Tier,
+            "manaSteal" | "lifeSteal" | "poison" => IdentificationUnit::PerThreeSeconds,
+            "manaRegen" => IdentificationUnit::PerFiveSeconds,
+            _ if key.starts_with("raw") || key.ends_with("Raw") => IdentificationUnit::Raw,
+            _ => IdentificationUnit::Percent,
+        };
+
+        let stripped = key.strip_prefix("raw").unwrap_or(key);
+        let stripped = stripped.strip_suffix("Raw").unwrap_or(stripped);
+        let name = match stripped {
+            "xpBonus" => "XP Bonus".to_string(),
+            _ => display_name(stripped),
+        };
+
+        Identification {
+            key: key.to_string(),
+            name,
+            unit,
+        }
+    }
+
+    pub fn format(&self, value: i32) -> String {
+        format!("{:+}{} {}", value, self.unit.suffix(), self.name)
+    }
+}
+
+// Splits a camelCase key into capitalised words, `walkSpeed` becomes "Walk Speed".
+fn display_name(key: &str) -> String {
+    let mut name = String::new();
+    for (i, c) in key.chars().enumerate() {
+        if i == 0 {
+            name.extend(c.to_uppercase());
+        } else if c.is_uppercase() {
+            name.push(' ');
+            name.push(c);
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
+
+impl ItemMetadata {
+    /// Looks up an identification key, keys missing from the catalogue still get a name and
+    /// unit worked out from the key.
+    pub fn identification(&self, key: &str) -> Identification {
+        self.identifications
+            .iter()
+            .find(|identification| identification.key == key)
+            .cloned()
+            .unwrap_or_else(|| Identification::from_key(key))
+    }
+
+    pub fn has_identification(&self, key: &str) -> bool {
+        self.identifications
+            .iter()
+            .any(|identification| identification.key == key)
+    }
+
+    fn has_type(&self, item_type: &str) -> bool {
+        self.filters.types.iter().any(|t| t == item_type)
+            || self
+                .filters
+                .subtypes
+                .values()
+                .flatten()
+                .any(|t| t == item_type)
+    }
+
+    fn has_tier(&self, tier: &ItemTier) -> bool {
+        let tiers = &self.filters.tiers;
+        match tier {
+            ItemTier::Rarity(rarity) => tiers.items.iter().any(|r| r == rarity),
+            ItemTier::Tier(tier) => {
+                tiers.ingredients.contains(tier) || tiers.materials.contains(tier)
+            }
+        }
+    }
+}
+
+impl ItemQuery {
+    /// Checks every filter against the catalogue so a typo fails before the search is sent.
+    ///
+    /// Filters the catalogue has no values for are not checked.
+    pub fn validate(&self, metadata: &ItemMetadata) -> Result<()> {
+        let invalid = |filter: &str, value: String| {
+            Err(WynnError::InvalidQuery {
+                filter: filter.to_string(),
+                value,
+            })
+        };
+
+        if let Some((min, max)) = self.level_range {
+            if min > max {
+                return invalid("level range", format!("{}-{}", min, max));
+            }
+        }
+        if !metadata.identifications.is_empty() {
+            if let Some(id) = self
+                .identifications
+                .iter()
+                .find(|id| !metadata.has_identification(id))
+            {
+                return invalid("identification", id.clone());
+            }
+        }
+        if !metadata.filters.types.is_empty() {
+            if let Some(t) = self.types.iter().find(|t| !metadata.has_type(t)) {
+                return invalid("type", t.clone());
+            }
+        }
+        if !metadata.filters.attack_speed.is_empty() {
+            if let Some(speed) = self
+                .attack_speed
+                .iter()
+                .find(|speed| !metadata.filters.attack_speed.contains(speed))
+            {
+                return invalid("attack speed", speed.clone());
+            }
+        }
+        let tiers = &metadata.filters.tiers;
+        if !(tiers.items.is_empty() && tiers.ingredients.is_empty() && tiers.materials.is_empty()) {
+            if let Some(tier) = self.tiers.iter().find(|tier| !metadata.has_tier(tier)) {
+                let value = match tier {
+                    ItemTier::Rarity(rarity) => rarity.clone(),
+                    ItemTier::Tier(tier) => tier.to_string(),
+                };
+                return invalid("tier", value);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Keyed for Item {
+    fn set_key(&mut self, key: String) {
+        if self.internal_name.is_empty() {
+            self.internal_name = key.clone();
+        }
+        self.name = key;
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawItem {
+    #[serde(default)]
+    internal_name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    rarity: Option<ItemRarity>,
+    #[serde(default)]
+    tier: Option<i32>,
+    #[serde(default)]
+    weapon_type: Option<String>,
+    #[serde(default)]
+    attack_speed: Option<String>,
+    #[serde(default)]
+    average_dps: Option<f32>,
+    #[serde(default)]
+    armour_type: Option<String>,
+    #[serde(default)]
+    armour_material: Option<String>,
+    #[serde(default)]
+    accessory_type: Option<String>,
+    #[serde(default)]
+    tome_type: Option<String>,
+    #[serde(default)]
+    tool_type: Option<String>,
+    #[serde(default)]
+    gathering_speed: Option<i32>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    requirements: ItemRequirements,
+    #[serde(default, deserialize_with = "null_as_default")]
+    base: HashMap<String, StatValue>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    identifications: HashMap<String, StatValue>,
+    #[serde(default)]
+    powder_slots: Option<i32>,
+    #[serde(default)]
+    lore: Option<String>,
+    #[serde(default)]
+    drop_restriction: Option<String>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    identified: bool,
+}
+
+impl From<RawItem> for Item {
+    fn from(raw: RawItem) -> Self {
+        let kind = match raw.kind.as_str() {
+            "weapon" => ItemKind::Weapon {
+                weapon_type: raw.weapon_type.unwrap_or_default(),
+                attack_speed: raw.attack_speed,
+                average_dps: raw.average_dps,
+            },
+            "armour" => ItemKind::Armour {
+                armour_type: raw.armour_type.unwrap_or_default(),
+                armour_material: raw.armour_material,
+            },
+            "accessory" => ItemKind::Accessory {
+                accessory_type: raw.accessory_type.unwrap_or_default(),
+            },
+            "tome" => ItemKind::Tome {
+                tome_type: raw.tome_type.unwrap_or_default(),
+            },
+            "charm" => ItemKind::Charm,
+            "ingredient" => ItemKind::Ingredient {
+                tier: raw.tier.unwrap_or_default(),
+            },
+            "material" => ItemKind::Material {
+                tier: raw.tier.unwrap_or_default(),
+            },
+            "tool" => ItemKind::Tool {
+                tool_type: raw.tool_type.unwrap_or_default(),
+                gathering_speed: raw.gathering_speed,
+            },
+            _ => ItemKind::Unknown(raw.kind),
+        };
+
+        Item {
+            // Filled in from the key the item is listed under.
+            name: String::new(),
+            internal_name: raw.internal_name,
+            kind,
+            rarity: raw.rarity,
+            requirements: raw.requirements,
+            base: raw.base,
+            identifications: raw.identifications,
+            powder_slots: raw.powder_slots,
+            lore: raw.lore,
+            drop_restriction: raw.drop_restriction,
+            identified: raw.identified,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawItemPage {
+    controller: RawController,
+    results: BTreeMap<String, Item>,
+}
+
+#[derive(Deserialize)]
+struct RawController {
+    count: i32,
+    pages: i32,
+    current: i32,
+    #[serde(default)]
+    prev: Option<i32>,
+    #[serde(default)]
+    next: Option<i32>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct RawItemMetadata {
+    identifications: Vec<String>,
+    filters: ItemFilters,
+}
+
+impl From<RawItemMetadata> for ItemMetadata {
+    fn from(raw: RawItemMetadata) -> Self {
+        ItemMetadata {
+            identifications: raw
+                .identifications
+                .iter()
+                .map(|key| Identification::from_key(key))
+                .collect(),
+            filters: raw.filters,
+        }
+    }
+}
+
+impl From<RawItemPage> for ItemPage {
+    fn from(data: RawItemPage) -> Self {
+        ItemPage {
+            items: into_keyed_vec(data.results),
+            count: data.controller.count,
+            pages: data.controller.pages,
+            current: data.controller.current,
+            previous: data.controller.prev,
+            next: data.controller.next,
+        }
+    }
+}
+
+impl WynnClient {
+    pub async fn get_item_database_page(&self, page: i32) -> Result<ItemPage> {
+        let data: RawItemPage = self
+            .get(Endpoint::new(["item", "database"]).query("page", page))
+            .await?;
+
+        Ok(data.into())
+    }
+
+    /// Walks the item database one page at a time, starting from the first page.
+    ///
+    /// The stream ends after the last page or the first error.
+    pub fn item_database_pages(&self) -> impl Stream<Item = Result<ItemPage>> {
+        let client = self.clone();
+
+        stream::try_unfold(Some(1), move |page| {
+            let client = client.clone();
+            async move {
+                let Some(page) = page else {
+                    return Ok(None);
+                };
+                let data = client.get_item_database_page(page).await?;
+                // Never go backwards, a broken `next` would otherwise loop forever.
+                let next = data.next.filter(|next| *next > data.current);
+
+                Ok(Some((data, next)))
+            }
+        })
+    }
+
+    pub async fn get_full_item_database(&self) -> Result<Vec<Item>> {
+        let items = self
+            .get(Endpoint::new(["item", "database"]).query("fullResult", "True"))
+            .await?;
+
+        Ok(into_keyed_vec(items))
+    }
+
+    pub async fn search_items(&self, query: &ItemQuery) -> Result<ItemPage> {
+        self.search_items_page(query, 1).await
+    }
+
+    pub async fn search_items_page(&self, query: &ItemQuery, page: i32) -> Result<ItemPage> {
+        let data: RawItemPage = self
+            .post(Endpoint::new(["item", "search"]).query("page", page), query)
+            .await?;
+
+        Ok(data.into())
+    }
+
+    // Matches item names only, use `search_items` to filter on anything else.
+    pub async fn quick_search_items(&self, query: &str) -> Result<Vec<Item>> {
+        let items = self.get(Endpoint::new(["item", "quick", query])).await?;
+
+        Ok(into_keyed_vec(items))
+    }
+
+    pub async fn get_item_metadata(&self) -> Result<ItemMetadata> {
+        self.get(Endpoint::new(["item", "metadata"])).await
+    }
+}
+
+pub async fn get_item_database_page(page: i32) -> Result<ItemPage> {
+    default_client()?.get_item_database_page(page).await
+}
+
+pub fn item_database_pages() -> impl Stream<Item = Result<ItemPage>> {
+    match default_client() {
+        Ok(client) => client.item_database_pages().left_stream(),
+        Err(e) => stream::once(async { Err(e) }).right_stream(),
+    }
+}
+
+pub async fn get_full_item_database() -> Result<Vec<Item>> {
+    default_client()?.get_full_item_database().await
+}
+
+pub async fn search_items(query: &ItemQuery) -> Result<ItemPage> {
+    default_client()?.search_items(query).await
+}
+
+pub async fn search_items_page(query: &ItemQuery, page: i32) -> Result<ItemPage> {
+    default_client()?.search_items_page(query, page).await
+}
+
+pub async fn quick_search_items(query: &str) -> Result<Vec<Item>> {
+    default_client()?.quick_search_items(query).await
+}
+
+pub async fn get_item_metadata() -> Result<ItemMetadata> {
+    default_client()?.get_item_metadata().await
+}
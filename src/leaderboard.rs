@@ -1,99 +1,731 @@
-use anyhow::Context;
-use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::client::{default_client, WynnClient};
+use crate::endpoint::Endpoint;
+use crate::error::{Result, WynnError};
+use crate::guild::SimpleGuildData;
+use crate::player::{LegacyRankColour, SimpleCharacterData};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
 
 #[derive(Debug)]
 pub struct Leaderboard {
     pub position: i32,
     pub name: String,
     pub uuid: String,
-    pub score: i32,
-    pub previous_ranking: i32,
-    // metadata, check all lb types for what is here
-    pub nickname: String,
-    pub character_uuid: String,
-    pub character_type: String,
-    pub rank: String,
-    pub rank_badge: String,
-    pub support_rank: String,
-    // legacy_rank_colour special type
-}
-
-// woodcuttingLevel: xp, playtime
-// miningLevel: xp, playtime
-// fishingLevel: xp, playtime
-// farmingLevel: xp, playtime
-// alchemismLevel: xp, playtime
-// armouringLevel: xp, playtime
-// cookingLevel: xp, playtime
-// jewelingLevel: xp, playtime
-// scribingLevel: xp, playtime
-// tailoringLevel: xp, playtime
-// weaponsmithingLevel: xp, playtime
-// woodworkingLevel: xp, playtime
-// professionsGlobalLevel: xp, playtime
-// combatGlobalLevel: xp, playtime
-// totalGlobalLevel: xp, playtime
-// playerContent: totalLevel, xp, playtime
-// combatSoloLevel: totalLevel, xp, playtime
-// professionsSoloLevel: totalLevel, xp, playtime
-// totalSoloLevel: totalLevel, xp, playtime
-// globalPlayerContent: playtime
-// hardcoreLegacyLevel: totalLevel, xp, playtime also an extra characterData field
-// nogCompletion: playtime
-// tccCompletion: playtime
-// nolCompletion: playtime
-// warsCompletion: playtime
-// ironmanContent: totalLevel, xp, playtime
-// tnaCompletion: playtime
-// ultimateIronmanContent: totalLevel, xp, playtime
-// hardcoreContent: totalLevel, xp, playtime also an extra characterData field
-// craftsmanContent: totalLevel, xp, playtime
-// huntedContent: xp, playtime
-// huicContent: totalLevel, xp, playtime also an extra characterData field
-// huichContent: totalLevel, xp, playtime also an extra characterData field
-// hichContent: totalLevel, xp, playtime also an extra characterData field
-// hicContent: totalLevel, xp, playtime also an extra characterData field
-
-// async fn fetch_leaderboard(lb_type: &str, limit: i32) -> anyhow::Result<Vec<Leaderboard>> {
-//     let url = format!("https://api.wynncraft.com/v3/leaderboards/{}?resultLimit={}", lb_type, limit);
-//
-//     let response = reqwest::get(url).await.context("Failed to make the API request")?;
-//
-//     let data: Value = response.json().await.context("Failed to parse the JSON response")?;
-//
-//
-// }
-
-// pub async fn get_leaderboard(lb_type: &str) -> anyhow::Result<Vec<Leaderboard>> {
-//     fetch_leaderboard(lb_type, 100).await
-// }
-
-// pub async fn get_leaderboard_with_limit(lb_type: &str, limit: i32) -> anyhow::Result<Vec<Leaderboard>> {
-//     fetch_leaderboard(lb_type, limit).await
-// }
-
-pub async fn get_leaderboard_types() -> anyhow::Result<Vec<String>> {
-    let url = "https://api.wynncraft.com/v3/leaderboards/types";
-
-    let response = reqwest::get(url)
-        .await
-        .context("Failed to make the API request")?;
+    pub score: i64,
+    pub previous_ranking: Option<i32>,
+    pub nickname: Option<String>,
+    pub character_uuid: Option<String>,
+    pub character_type: Option<String>,
+    pub rank: Option<String>,
+    pub rank_badge: Option<String>,
+    pub support_rank: Option<String>,
+    pub legacy_rank_colour: Option<LegacyRankColour>,
+    pub metadata: LeaderboardMetadata,
+}
 
-    let data: Value = response
-        .json()
-        .await
-        .context("Failed to parse the JSON response")?;
+/// Entries on the guild boards (guildLevel, guildTerritories, guildWars).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GuildLeaderboardEntry {
+    #[serde(skip)]
+    pub position: i32,
+    pub uuid: String,
+    pub name: String,
+    pub prefix: String,
+    #[serde(default)]
+    pub level: i32,
+    #[serde(default)]
+    pub xp: i64,
+    #[serde(default)]
+    pub territories: i32,
+    #[serde(default)]
+    pub wars: i32,
+    #[serde(default)]
+    pub members: i32,
+    #[serde(default)]
+    pub previous_ranking: Option<i32>,
+}
+
+impl GuildLeaderboardEntry {
+    pub fn guild(&self) -> SimpleGuildData {
+        SimpleGuildData {
+            uuid: self.uuid.clone(),
+            name: self.name.clone(),
+            prefix: self.prefix.clone(),
+        }
+    }
+}
+
+/// Which variant an entry gets depends on the board:
+/// - `Playtime`: globalPlayerContent and the raid/war completion boards
+/// - `Level`: the profession and global level boards, huntedContent
+/// - `Content`: playerContent, the solo level boards and most gamemode content boards
+/// - `Hardcore`: hardcoreLegacyLevel, hardcoreContent, huicContent, huichContent, hichContent and
+///   hicContent, which also describe the character the score was set on
+///
+/// An entry missing a field its board always sends is a decode error. Boards this crate doesn't
+/// know get the variant matching the fields they sent, or `Empty`.
+#[derive(Debug)]
+pub enum LeaderboardMetadata {
+    Playtime {
+        playtime: f32,
+    },
+    Level {
+        xp: i64,
+        playtime: f32,
+    },
+    Content {
+        total_level: i32,
+        xp: i64,
+        playtime: f32,
+    },
+    Hardcore {
+        total_level: i32,
+        xp: i64,
+        playtime: f32,
+        character: SimpleCharacterData,
+    },
+    Empty,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawLeaderboard {
+    #[serde(default)]
+    name: String,
+    uuid: String,
+    score: i64,
+    #[serde(default)]
+    previous_ranking: Option<i32>,
+    #[serde(default)]
+    nickname: Option<String>,
+    #[serde(default)]
+    character_uuid: Option<String>,
+    #[serde(default)]
+    character_type: Option<String>,
+    #[serde(default)]
+    rank: Option<String>,
+    #[serde(default)]
+    rank_badge: Option<String>,
+    #[serde(default)]
+    support_rank: Option<String>,
+    #[serde(default)]
+    legacy_rank_colour: Option<LegacyRankColour>,
+    #[serde(default)]
+    metadata: Option<RawMetadata>,
+    #[serde(default)]
+    character_data: Option<SimpleCharacterData>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawMetadata {
+    total_level: Option<i32>,
+    xp: Option<i64>,
+    playtime: Option<f32>,
+}
+
+// Which `LeaderboardMetadata` variant a board's entries carry, see the table on the enum.
+#[derive(Clone, Copy)]
+enum MetadataKind {
+    Playtime,
+    Level,
+    Content,
+    Hardcore,
+}
+
+impl LeaderboardType {
+    // `None` for guild boards, which have their own entries, and for boards this crate doesn't
+    // know, whose metadata is read from whatever they send.
+    fn metadata_kind(&self) -> Option<MetadataKind> {
+        match self {
+            LeaderboardType::GlobalPlayerContent
+            | LeaderboardType::WarsCompletion
+            | LeaderboardType::NogCompletion
+            | LeaderboardType::TccCompletion
+            | LeaderboardType::NolCompletion
+            | LeaderboardType::TnaCompletion => Some(MetadataKind::Playtime),
+            LeaderboardType::WoodcuttingLevel
+            | LeaderboardType::MiningLevel
+            | LeaderboardType::FishingLevel
+            | LeaderboardType::FarmingLevel
+            | LeaderboardType::AlchemismLevel
+            | LeaderboardType::ArmouringLevel
+            | LeaderboardType::CookingLevel
+            | LeaderboardType::JewelingLevel
+            | LeaderboardType::ScribingLevel
+            | LeaderboardType::TailoringLevel
+            | LeaderboardType::WeaponsmithingLevel
+            | LeaderboardType::WoodworkingLevel
+            | LeaderboardType::ProfessionsGlobalLevel
+            | LeaderboardType::CombatGlobalLevel
+            | LeaderboardType::TotalGlobalLevel
+            | LeaderboardType::HuntedContent => Some(MetadataKind::Level),
+            LeaderboardType::PlayerContent
+            | LeaderboardType::ProfessionsSoloLevel
+            | LeaderboardType::CombatSoloLevel
+            | LeaderboardType::TotalSoloLevel
+            | LeaderboardType::IronmanContent
+            | LeaderboardType::UltimateIronmanContent
+            | LeaderboardType::CraftsmanContent => Some(MetadataKind::Content),
+            LeaderboardType::HardcoreLegacyLevel
+            | LeaderboardType::HardcoreContent
+            | LeaderboardType::HicContent
+            | LeaderboardType::HichContent
+            | LeaderboardType::HuicContent
+            | LeaderboardType::HuichContent => Some(MetadataKind::Hardcore),
+            LeaderboardType::GuildLevel
+            | LeaderboardType::GuildTerritories
+            | LeaderboardType::GuildWars
+            | LeaderboardType::Unknown(_) => None,
+        }
+    }
+}
+
+// The variant comes from the board, a field that board always sends is an error when missing.
+fn leaderboard_metadata(
+    position: i32,
+    lb_type: &LeaderboardType,
+    metadata: Option<RawMetadata>,
+    character: Option<SimpleCharacterData>,
+) -> Result<LeaderboardMetadata> {
+    let Some(kind) = lb_type.metadata_kind() else {
+        return Ok(guessed_metadata(metadata, character));
+    };
+
+    let metadata = metadata.unwrap_or_default();
+    let missing = |field: &str| {
+        WynnError::decode(
+            format!("{}.metadata.{}", position, field),
+            format!("missing field `{}`", field),
+        )
+    };
+    let playtime = metadata.playtime.ok_or_else(|| missing("playtime"))?;
+
+    Ok(match kind {
+        MetadataKind::Playtime => LeaderboardMetadata::Playtime { playtime },
+        MetadataKind::Level => LeaderboardMetadata::Level {
+            xp: metadata.xp.ok_or_else(|| missing("xp"))?,
+            playtime,
+        },
+        MetadataKind::Content => LeaderboardMetadata::Content {
+            total_level: metadata.total_level.ok_or_else(|| missing("totalLevel"))?,
+            xp: metadata.xp.ok_or_else(|| missing("xp"))?,
+            playtime,
+        },
+        MetadataKind::Hardcore => LeaderboardMetadata::Hardcore {
+            total_level: metadata.total_level.ok_or_else(|| missing("totalLevel"))?,
+            xp: metadata.xp.ok_or_else(|| missing("xp"))?,
+            playtime,
+            character: character.ok_or_else(|| {
+                WynnError::decode(
+                    format!("{}.characterData", position),
+                    "missing field `characterData`",
+                )
+            })?,
+        },
+    })
+}
+
+// Boards this crate doesn't know get the richest variant whose fields are all present.
+fn guessed_metadata(
+    metadata: Option<RawMetadata>,
+    character: Option<SimpleCharacterData>,
+) -> LeaderboardMetadata {
+    let metadata = metadata.unwrap_or_default();
+
+    match (
+        metadata.total_level,
+        metadata.xp,
+        metadata.playtime,
+        character,
+    ) {
+        (Some(total_level), Some(xp), Some(playtime), Some(character)) => {
+            LeaderboardMetadata::Hardcore {
+                total_level,
+                xp,
+                playtime,
+                character,
+            }
+        }
+        (Some(total_level), Some(xp), Some(playtime), None) => LeaderboardMetadata::Content {
+            total_level,
+            xp,
+            playtime,
+        },
+        (_, Some(xp), Some(playtime), _) => LeaderboardMetadata::Level { xp, playtime },
+        (_, _, Some(playtime), _) => LeaderboardMetadata::Playtime { playtime },
+        _ => LeaderboardMetadata::Empty,
+    }
+}
+
+impl Leaderboard {
+    fn from_raw(position: i32, lb_type: &LeaderboardType, raw: RawLeaderboard) -> Result<Self> {
+        Ok(Leaderboard {
+            position,
+            name: raw.name,
+            uuid: raw.uuid,
+            score: raw.score,
+            previous_ranking: raw.previous_ranking,
+            nickname: raw.nickname,
+            character_uuid: raw.character_uuid,
+            character_type: raw.character_type,
+            rank: raw.rank,
+            rank_badge: raw.rank_badge,
+            support_rank: raw.support_rank,
+            legacy_rank_colour: raw.legacy_rank_colour,
+            metadata: leaderboard_metadata(position, lb_type, raw.metadata, raw.character_data)?,
+        })
+    }
+}
+
+/// A leaderboard that can be passed to `get_leaderboard`.
+///
+/// Boards this crate doesn't know about yet come back as `Unknown` with their API name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(from = "String")]
+pub enum LeaderboardType {
+    WoodcuttingLevel,
+    MiningLevel,
+    FishingLevel,
+    FarmingLevel,
+    AlchemismLevel,
+    ArmouringLevel,
+    CookingLevel,
+    JewelingLevel,
+    ScribingLevel,
+    TailoringLevel,
+    WeaponsmithingLevel,
+    WoodworkingLevel,
+    ProfessionsGlobalLevel,
+    ProfessionsSoloLevel,
+    CombatGlobalLevel,
+    CombatSoloLevel,
+    TotalGlobalLevel,
+    TotalSoloLevel,
+    PlayerContent,
+    GlobalPlayerContent,
+    WarsCompletion,
+    NogCompletion,
+    TccCompletion,
+    NolCompletion,
+    TnaCompletion,
+    GuildLevel,
+    GuildTerritories,
+    GuildWars,
+    HardcoreLegacyLevel,
+    HardcoreContent,
+    IronmanContent,
+    UltimateIronmanContent,
+    CraftsmanContent,
+    HuntedContent,
+    HicContent,
+    HichContent,
+    HuicContent,
+    HuichContent,
+    Unknown(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LeaderboardCategory {
+    Profession,
+    Combat,
+    Content,
+    RaidCompletion,
+    Guild,
+    Gamemode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Gamemode {
+    Hardcore,
+    Ironman,
+    UltimateIronman,
+    Craftsman,
+    Hunted,
+}
+
+impl Gamemode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Gamemode::Hardcore => "hardcore",
+            Gamemode::Ironman => "ironman",
+            Gamemode::UltimateIronman => "ultimate_ironman",
+            Gamemode::Craftsman => "craftsman",
+            Gamemode::Hunted => "hunted",
+        }
+    }
+
+    /// Takes the names used in a character's `gamemode` list.
+    pub fn from_name(name: &str) -> Option<Gamemode> {
+        match name {
+            "hardcore" => Some(Gamemode::Hardcore),
+            "ironman" => Some(Gamemode::Ironman),
+            "ultimate_ironman" => Some(Gamemode::UltimateIronman),
+            "craftsman" => Some(Gamemode::Craftsman),
+            "hunted" => Some(Gamemode::Hunted),
+            _ => None,
+        }
+    }
+}
+
+impl LeaderboardType {
+    pub const ALL: &'static [LeaderboardType] = &[
+        LeaderboardType::WoodcuttingLevel,
+        LeaderboardType::MiningLevel,
+        LeaderboardType::FishingLevel,
+        LeaderboardType::FarmingLevel,
+        LeaderboardType::AlchemismLevel,
+        LeaderboardType::ArmouringLevel,
+        LeaderboardType::CookingLevel,
+        LeaderboardType::JewelingLevel,
+        LeaderboardType::ScribingLevel,
+        LeaderboardType::TailoringLevel,
+        LeaderboardType::WeaponsmithingLevel,
+        LeaderboardType::WoodworkingLevel,
+        LeaderboardType::ProfessionsGlobalLevel,
+        LeaderboardType::ProfessionsSoloLevel,
+        LeaderboardType::CombatGlobalLevel,
+        LeaderboardType::CombatSoloLevel,
+        LeaderboardType::TotalGlobalLevel,
+        LeaderboardType::TotalSoloLevel,
+        LeaderboardType::PlayerContent,
+        LeaderboardType::GlobalPlayerContent,
+        LeaderboardType::WarsCompletion,
+        LeaderboardType::NogCompletion,
+        LeaderboardType::TccCompletion,
+        LeaderboardType::NolCompletion,
+        LeaderboardType::TnaCompletion,
+        LeaderboardType::GuildLevel,
+        LeaderboardType::GuildTerritories,
+        LeaderboardType::GuildWars,
+        LeaderboardType::HardcoreLegacyLevel,
+        LeaderboardType::HardcoreContent,
+        LeaderboardType::IronmanContent,
+        LeaderboardType::UltimateIronmanContent,
+        LeaderboardType::CraftsmanContent,
+        LeaderboardType::HuntedContent,
+        LeaderboardType::HicContent,
+        LeaderboardType::HichContent,
+        LeaderboardType::HuicContent,
+        LeaderboardType::HuichContent,
+    ];
 
-    let lb_types_array = data
-        .as_array()
-        .expect("Expected marker data to be a JSON array");
+    pub fn as_str(&self) -> &str {
+        match self {
+            LeaderboardType::WoodcuttingLevel => "woodcuttingLevel",
+            LeaderboardType::MiningLevel => "miningLevel",
+            LeaderboardType::FishingLevel => "fishingLevel",
+            LeaderboardType::FarmingLevel => "farmingLevel",
+            LeaderboardType::AlchemismLevel => "alchemismLevel",
+            LeaderboardType::ArmouringLevel => "armouringLevel",
+            LeaderboardType::CookingLevel => "cookingLevel",
+            LeaderboardType::JewelingLevel => "jewelingLevel",
+            LeaderboardType::ScribingLevel => "scribingLevel",
+            LeaderboardType::TailoringLevel => "tailoringLevel",
+            LeaderboardType::WeaponsmithingLevel => "weaponsmithingLevel",
+            LeaderboardType::WoodworkingLevel => "woodworkingLevel",
+            LeaderboardType::ProfessionsGlobalLevel => "professionsGlobalLevel",
+            LeaderboardType::ProfessionsSoloLevel => "professionsSoloLevel",
+            LeaderboardType::CombatGlobalLevel => "combatGlobalLevel",
+            LeaderboardType::CombatSoloLevel => "combatSoloLevel",
+            LeaderboardType::TotalGlobalLevel => "totalGlobalLevel",
+            LeaderboardType::TotalSoloLevel => "totalSoloLevel",
+            LeaderboardType::PlayerContent => "playerContent",
+            LeaderboardType::GlobalPlayerContent => "globalPlayerContent",
+            LeaderboardType::WarsCompletion => "warsCompletion",
+            LeaderboardType::NogCompletion => "nogCompletion",
+            LeaderboardType::TccCompletion => "tccCompletion",
+            LeaderboardType::NolCompletion => "nolCompletion",
+            LeaderboardType::TnaCompletion => "tnaCompletion",
+            LeaderboardType::GuildLevel => "guildLevel",
+            LeaderboardType::GuildTerritories => "guildTerritories",
+            LeaderboardType::GuildWars => "guildWars",
+            LeaderboardType::HardcoreLegacyLevel => "hardcoreLegacyLevel",
+            LeaderboardType::HardcoreContent => "hardcoreContent",
+            LeaderboardType::IronmanContent => "ironmanContent",
+            LeaderboardType::UltimateIronmanContent => "ultimateIronmanContent",
+            LeaderboardType::CraftsmanContent => "craftsmanContent",
+            LeaderboardType::HuntedContent => "huntedContent",
+            LeaderboardType::HicContent => "hicContent",
+            LeaderboardType::HichContent => "hichContent",
+            LeaderboardType::HuicContent => "huicContent",
+            LeaderboardType::HuichContent => "huichContent",
+            LeaderboardType::Unknown(name) => name,
+        }
+    }
 
-    let mut lb_types = Vec::new();
-    for lb_type_value in lb_types_array {
-        if let Some(lb_type) = lb_type_value.as_str() {
-            lb_types.push(lb_type.to_string());
+    pub fn category(&self) -> Option<LeaderboardCategory> {
+        match self {
+            LeaderboardType::WoodcuttingLevel
+            | LeaderboardType::MiningLevel
+            | LeaderboardType::FishingLevel
+            | LeaderboardType::FarmingLevel
+            | LeaderboardType::AlchemismLevel
+            | LeaderboardType::ArmouringLevel
+            | LeaderboardType::CookingLevel
+            | LeaderboardType::JewelingLevel
+            | LeaderboardType::ScribingLevel
+            | LeaderboardType::TailoringLevel
+            | LeaderboardType::WeaponsmithingLevel
+            | LeaderboardType::WoodworkingLevel
+            | LeaderboardType::ProfessionsGlobalLevel
+            | LeaderboardType::ProfessionsSoloLevel => Some(LeaderboardCategory::Profession),
+            LeaderboardType::CombatGlobalLevel | LeaderboardType::CombatSoloLevel => {
+                Some(LeaderboardCategory::Combat)
+            }
+            LeaderboardType::TotalGlobalLevel
+            | LeaderboardType::TotalSoloLevel
+            | LeaderboardType::PlayerContent
+            | LeaderboardType::GlobalPlayerContent
+            | LeaderboardType::WarsCompletion => Some(LeaderboardCategory::Content),
+            LeaderboardType::NogCompletion
+            | LeaderboardType::TccCompletion
+            | LeaderboardType::NolCompletion
+            | LeaderboardType::TnaCompletion => Some(LeaderboardCategory::RaidCompletion),
+            LeaderboardType::GuildLevel
+            | LeaderboardType::GuildTerritories
+            | LeaderboardType::GuildWars => Some(LeaderboardCategory::Guild),
+            LeaderboardType::HardcoreLegacyLevel
+            | LeaderboardType::HardcoreContent
+            | LeaderboardType::IronmanContent
+            | LeaderboardType::UltimateIronmanContent
+            | LeaderboardType::CraftsmanContent
+            | LeaderboardType::HuntedContent
+            | LeaderboardType::HicContent
+            | LeaderboardType::HichContent
+            | LeaderboardType::HuicContent
+            | LeaderboardType::HuichContent => Some(LeaderboardCategory::Gamemode),
+            LeaderboardType::Unknown(_) => None,
         }
     }
 
-    Ok(lb_types)
+    /// The gamemodes a character needs to appear on the board, empty for the regular boards.
+    pub fn gamemodes(&self) -> &'static [Gamemode] {
+        match self {
+            LeaderboardType::HardcoreLegacyLevel => &[Gamemode::Hardcore],
+            LeaderboardType::HardcoreContent => &[Gamemode::Hardcore],
+            LeaderboardType::IronmanContent => &[Gamemode::Ironman],
+            LeaderboardType::UltimateIronmanContent => &[Gamemode::UltimateIronman],
+            LeaderboardType::CraftsmanContent => &[Gamemode::Craftsman],
+            LeaderboardType::HuntedContent => &[Gamemode::Hunted],
+            LeaderboardType::HicContent => {
+                &[Gamemode::Hardcore, Gamemode::Ironman, Gamemode::Craftsman]
+            }
+            LeaderboardType::HichContent => &[
+                Gamemode::Hardcore,
+                Gamemode::Ironman,
+                Gamemode::Craftsman,
+                Gamemode::Hunted,
+            ],
+            LeaderboardType::HuicContent => &[
+                Gamemode::Hardcore,
+                Gamemode::UltimateIronman,
+                Gamemode::Craftsman,
+            ],
+            LeaderboardType::HuichContent => &[
+                Gamemode::Hardcore,
+                Gamemode::UltimateIronman,
+                Gamemode::Craftsman,
+                Gamemode::Hunted,
+            ],
+            _ => &[],
+        }
+    }
+
+    /// Every gamemode board a character with these gamemodes can appear on.
+    pub fn for_gamemodes(gamemodes: &[String]) -> Vec<LeaderboardType> {
+        let gamemodes: Vec<Gamemode> = gamemodes
+            .iter()
+            .filter_map(|name| Gamemode::from_name(name))
+            .collect();
+
+        LeaderboardType::ALL
+            .iter()
+            .filter(|lb_type| {
+                let required = lb_type.gamemodes();
+                !required.is_empty() && required.iter().all(|mode| gamemodes.contains(mode))
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+impl From<&str> for LeaderboardType {
+    fn from(name: &str) -> Self {
+        match name {
+            "woodcuttingLevel" => LeaderboardType::WoodcuttingLevel,
+            "miningLevel" => LeaderboardType::MiningLevel,
+            "fishingLevel" => LeaderboardType::FishingLevel,
+            "farmingLevel" => LeaderboardType::FarmingLevel,
+            "alchemismLevel" => LeaderboardType::AlchemismLevel,
+            "armouringLevel" => LeaderboardType::ArmouringLevel,
+            "cookingLevel" => LeaderboardType::CookingLevel,
+            "jewelingLevel" => LeaderboardType::JewelingLevel,
+            "scribingLevel" => LeaderboardType::ScribingLevel,
+            "tailoringLevel" => LeaderboardType::TailoringLevel,
+            "weaponsmithingLevel" => LeaderboardType::WeaponsmithingLevel,
+            "woodworkingLevel" => LeaderboardType::WoodworkingLevel,
+            "professionsGlobalLevel" => LeaderboardType::ProfessionsGlobalLevel,
+            "professionsSoloLevel" => LeaderboardType::ProfessionsSoloLevel,
+            "combatGlobalLevel" => LeaderboardType::CombatGlobalLevel,
+            "combatSoloLevel" => LeaderboardType::CombatSoloLevel,
+            "totalGlobalLevel" => LeaderboardType::TotalGlobalLevel,
+            "totalSoloLevel" => LeaderboardType::TotalSoloLevel,
+            "playerContent" => LeaderboardType::PlayerContent,
+            "globalPlayerContent" => LeaderboardType::GlobalPlayerContent,
+            "warsCompletion" => LeaderboardType::WarsCompletion,
+            "nogCompletion" => LeaderboardType::NogCompletion,
+            "tccCompletion" => LeaderboardType::TccCompletion,
+            "nolCompletion" => LeaderboardType::NolCompletion,
+            "tnaCompletion" => LeaderboardType::TnaCompletion,
+            "guildLevel" => LeaderboardType::GuildLevel,
+            "guildTerritories" => LeaderboardType::GuildTerritories,
+            "guildWars" => LeaderboardType::GuildWars,
+            "hardcoreLegacyLevel" => LeaderboardType::HardcoreLegacyLevel,
+            "hardcoreContent" => LeaderboardType::HardcoreContent,
+            "ironmanContent" => LeaderboardType::IronmanContent,
+            "ultimateIronmanContent" => LeaderboardType::UltimateIronmanContent,
+            "craftsmanContent" => LeaderboardType::CraftsmanContent,
+            "huntedContent" => LeaderboardType::HuntedContent,
+            "hicContent" => LeaderboardType::HicContent,
+            "hichContent" => LeaderboardType::HichContent,
+            "huicContent" => LeaderboardType::HuicContent,
+            "huichContent" => LeaderboardType::HuichContent,
+            _ => LeaderboardType::Unknown(name.to_string()),
+        }
+    }
+}
+
+impl From<String> for LeaderboardType {
+    fn from(name: String) -> Self {
+        LeaderboardType::from(name.as_str())
+    }
+}
+
+impl From<&LeaderboardType> for LeaderboardType {
+    fn from(lb_type: &LeaderboardType) -> Self {
+        lb_type.clone()
+    }
+}
+
+impl fmt::Display for LeaderboardType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl WynnClient {
+    pub async fn get_leaderboard(
+        &self,
+        lb_type: impl Into<LeaderboardType>,
+    ) -> Result<Vec<Leaderboard>> {
+        self.get_leaderboard_with_limit(lb_type, 100).await
+    }
+
+    pub async fn get_leaderboard_with_limit(
+        &self,
+        lb_type: impl Into<LeaderboardType>,
+        limit: i32,
+    ) -> Result<Vec<Leaderboard>> {
+        let lb_type = lb_type.into();
+        if lb_type.category() == Some(LeaderboardCategory::Guild) {
+            return Err(WynnError::GuildLeaderboard {
+                board: lb_type.as_str().to_string(),
+            });
+        }
+
+        let entries: BTreeMap<i32, RawLeaderboard> =
+            self.fetch_leaderboard(lb_type.clone(), limit).await?;
+
+        entries
+            .into_iter()
+            .map(|(position, raw)| Leaderboard::from_raw(position, &lb_type, raw))
+            .collect()
+    }
+
+    pub async fn get_guild_leaderboard(
+        &self,
+        lb_type: impl Into<LeaderboardType>,
+    ) -> Result<Vec<GuildLeaderboardEntry>> {
+        self.get_guild_leaderboard_with_limit(lb_type, 100).await
+    }
+
+    pub async fn get_guild_leaderboard_with_limit(
+        &self,
+        lb_type: impl Into<LeaderboardType>,
+        limit: i32,
+    ) -> Result<Vec<GuildLeaderboardEntry>> {
+        let lb_type = lb_type.into();
+        // Boards this crate doesn't know may be guild boards, so only known player boards fail.
+        if lb_type
+            .category()
+            .is_some_and(|category| category != LeaderboardCategory::Guild)
+        {
+            return Err(WynnError::PlayerLeaderboard {
+                board: lb_type.as_str().to_string(),
+            });
+        }
+
+        let entries: BTreeMap<i32, GuildLeaderboardEntry> =
+            self.fetch_leaderboard(lb_type, limit).await?;
+
+        Ok(entries
+            .into_iter()
+            .map(|(position, mut entry)| {
+                entry.position = position;
+                entry
+            })
+            .collect())
+    }
+
+    // Entries are keyed by their position on the board.
+    async fn fetch_leaderboard<T: DeserializeOwned>(
+        &self,
+        lb_type: impl Into<LeaderboardType>,
+        limit: i32,
+    ) -> Result<BTreeMap<i32, T>> {
+        let lb_type = lb_type.into();
+        self.get(Endpoint::new(["leaderboards", lb_type.as_str()]).query("resultLimit", limit))
+            .await
+    }
+
+    pub async fn get_leaderboard_types(&self) -> Result<Vec<LeaderboardType>> {
+        self.get(Endpoint::new(["leaderboards", "types"])).await
+    }
+}
+
+pub async fn get_leaderboard(lb_type: impl Into<LeaderboardType>) -> Result<Vec<Leaderboard>> {
+    default_client()?.get_leaderboard(lb_type).await
+}
+
+pub async fn get_leaderboard_with_limit(
+    lb_type: impl Into<LeaderboardType>,
+    limit: i32,
+) -> Result<Vec<Leaderboard>> {
+    default_client()?
+        .get_leaderboard_with_limit(lb_type, limit)
+        .await
+}
+
+pub async fn get_guild_leaderboard(
+    lb_type: impl Into<LeaderboardType>,
+) -> Result<Vec<GuildLeaderboardEntry>> {
+    default_client()?.get_guild_leaderboard(lb_type).await
+}
+
+pub async fn get_guild_leaderboard_with_limit(
+    lb_type: impl Into<LeaderboardType>,
+    limit: i32,
+) -> Result<Vec<GuildLeaderboardEntry>> {
+    default_client()?
+        .get_guild_leaderboard_with_limit(lb_type, limit)
+        .await
+}
+
+pub async fn get_leaderboard_types() -> Result<Vec<LeaderboardType>> {
+    default_client()?.get_leaderboard_types().await
 }
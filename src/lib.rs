@@ -1,19 +1,43 @@
+mod ability;
+mod cache;
 mod classes;
+mod client;
+mod decode;
+mod disk_cache;
+mod endpoint;
+mod error;
 mod guild;
+mod identifier;
+mod item;
 mod leaderboard;
 mod map;
 mod news;
 mod player;
+mod rate_limit;
+mod retry;
+mod search;
+mod timestamp;
 
+pub use ability::*;
+pub use cache::CacheConfig;
 pub use classes::*;
+pub use client::*;
+pub use disk_cache::StoredResponse;
+pub use error::*;
 pub use guild::*;
+pub use identifier::*;
+pub use item::*;
 pub use leaderboard::*;
 pub use map::*;
 pub use news::*;
 pub use player::*;
-use serde_json::Value;
+pub use rate_limit::RateLimit;
+pub use retry::RetryPolicy;
+pub use search::*;
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+pub use timestamp::Timestamp;
 
-#[allow(dead_code)]
 #[derive(Debug)]
 pub struct Location {
     x: i32,
@@ -21,20 +45,68 @@ pub struct Location {
     z: i32,
 }
 
-fn json_to_location(value: &Value) -> Location {
-    let loc = value.as_array().unwrap();
+impl Location {
+    pub fn x(&self) -> i32 {
+        self.x
+    }
 
-    if loc.len() == 3 {
-        Location {
-            x: loc[0].as_i64().unwrap() as i32,
-            y: Some(loc[1].as_i64().unwrap() as i32),
-            z: loc[2].as_i64().unwrap() as i32,
-        }
-    } else {
-        Location {
-            x: loc[0].as_i64().unwrap() as i32,
-            y: None,
-            z: loc[1].as_i64().unwrap() as i32,
+    // Territory corners are only given on the horizontal plane.
+    pub fn y(&self) -> Option<i32> {
+        self.y
+    }
+
+    pub fn z(&self) -> i32 {
+        self.z
+    }
+
+    /// Distance on the horizontal plane, height is ignored as not every location has one.
+    pub fn distance(&self, other: &Location) -> f64 {
+        let dx = f64::from(self.x) - f64::from(other.x);
+        let dz = f64::from(self.z) - f64::from(other.z);
+        dx.hypot(dz)
+    }
+}
+
+// Locations are sent as `[x, z]` or `[x, y, z]` arrays.
+impl<'de> Deserialize<'de> for Location {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct LocationVisitor;
+
+        impl<'de> Visitor<'de> for LocationVisitor {
+            type Value = Location;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("an array of two or three coordinates")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Location, A::Error> {
+                let x = seq
+                    .next_element()?
+                    .ok_or_else(|| Error::invalid_length(0, &self))?;
+                let second = seq
+                    .next_element()?
+                    .ok_or_else(|| Error::invalid_length(1, &self))?;
+
+                match seq.next_element()? {
+                    Some(z) => {
+                        if seq.next_element::<serde::de::IgnoredAny>()?.is_some() {
+                            return Err(Error::invalid_length(4, &self));
+                        }
+                        Ok(Location {
+                            x,
+                            y: Some(second),
+                            z,
+                        })
+                    }
+                    None => Ok(Location {
+                        x,
+                        y: None,
+                        z: second,
+                    }),
+                }
+            }
         }
+
+        deserializer.deserialize_seq(LocationVisitor)
     }
 }
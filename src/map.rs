@@ -1,87 +1,150 @@
+use crate::client::{default_client, WynnClient};
+use crate::decode::{required_string_or_number, string_or_number};
+use crate::endpoint::Endpoint;
+use crate::error::Result;
 use crate::Location;
-use anyhow::Context;
-use serde_json::Value;
+use serde::Deserialize;
 
-#[derive(Debug)]
+#[derive(Debug, Deserialize)]
+#[serde(from = "RawMarker")]
 pub struct Marker {
     pub name: String,
     pub icon: String,
     pub location: Location,
 }
 
-pub async fn get_map_markers() -> anyhow::Result<Vec<Marker>> {
-    let url = "https://api.wynncraft.com/v3/map/locations/markers";
-
-    let response = reqwest::get(url)
-        .await
-        .context("Failed to make the API request")?;
-
-    let data: Value = response
-        .json()
-        .await
-        .context("Failed to parse the JSON response")?;
-
-    let markers_array = data
-        .as_array()
-        .expect("Expected marker data to be a JSON array");
-
-    let mut markers_data = Vec::new();
-    for marker_value in markers_array {
-        if let Some(marker_object) = marker_value.as_object() {
-            let name = marker_object
-                .get("name")
-                .and_then(Value::as_str)
-                .unwrap_or_default()
-                .to_string();
-            let icon = marker_object
-                .get("icon")
-                .and_then(Value::as_str)
-                .unwrap_or_default()
-                .to_string();
-            let x = marker_object["x"]
-                .as_str()
-                .unwrap_or("0")
-                .parse::<i32>()
-                .context("Failed to parse 'x' coordinate to i32")?;
-            let y = marker_object["y"]
-                .as_str()
-                .unwrap_or("0")
-                .parse::<i32>()
-                .context("Failed to parse 'y' coordinate to i32")?;
-            let z = marker_object["z"]
-                .as_str()
-                .unwrap_or("0")
-                .parse::<i32>()
-                .context("Failed to parse 'z' coordinate to i32")?;
-
-            let location = Location { x, y: Some(y), z };
-            markers_data.push(Marker {
-                name,
-                icon,
-                location,
-            });
+#[derive(Deserialize)]
+struct RawMarker {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    icon: String,
+    #[serde(default, deserialize_with = "string_or_number")]
+    x: i32,
+    #[serde(default, deserialize_with = "string_or_number")]
+    y: i32,
+    #[serde(default, deserialize_with = "string_or_number")]
+    z: i32,
+}
+
+impl From<RawMarker> for Marker {
+    fn from(raw: RawMarker) -> Self {
+        Marker {
+            name: raw.name,
+            icon: raw.icon,
+            location: Location {
+                x: raw.x,
+                y: Some(raw.y),
+                z: raw.z,
+            },
+        }
+    }
+}
+
+/// A player's live position, only players the API key can see are listed.
+#[derive(Debug, Deserialize)]
+#[serde(from = "RawPlayerLocation")]
+pub struct PlayerLocation {
+    pub uuid: String,
+    pub name: String,
+    pub nickname: Option<String>,
+    pub world: String,
+    pub location: Location,
+    // Only sent for players related to the owner of the API key.
+    pub friend: Option<bool>,
+    pub party: Option<bool>,
+}
+
+impl PlayerLocation {
+    pub fn nearest_marker<'a>(&self, markers: &'a [Marker]) -> Option<&'a Marker> {
+        markers.iter().min_by(|a, b| {
+            self.location
+                .distance(&a.location)
+                .total_cmp(&self.location.distance(&b.location))
+        })
+    }
+}
+
+/// Pairs every player with the marker closest to them, `None` when there are no markers.
+pub fn nearest_markers<'a>(
+    players: &'a [PlayerLocation],
+    markers: &'a [Marker],
+) -> Vec<(&'a PlayerLocation, Option<&'a Marker>)> {
+    players
+        .iter()
+        .map(|player| (player, player.nearest_marker(markers)))
+        .collect()
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPlayerLocation {
+    uuid: String,
+    name: String,
+    #[serde(default)]
+    nickname: Option<String>,
+    server: String,
+    #[serde(deserialize_with = "required_string_or_number")]
+    x: i32,
+    #[serde(deserialize_with = "required_string_or_number")]
+    y: i32,
+    #[serde(deserialize_with = "required_string_or_number")]
+    z: i32,
+    #[serde(default)]
+    friend: Option<bool>,
+    #[serde(default)]
+    party: Option<bool>,
+}
+
+impl From<RawPlayerLocation> for PlayerLocation {
+    fn from(raw: RawPlayerLocation) -> Self {
+        PlayerLocation {
+            uuid: raw.uuid,
+            name: raw.name,
+            nickname: raw.nickname,
+            world: raw.server,
+            location: Location {
+                x: raw.x,
+                y: Some(raw.y),
+                z: raw.z,
+            },
+            friend: raw.friend,
+            party: raw.party,
         }
     }
+}
 
-    Ok(markers_data)
+#[derive(Deserialize)]
+struct QuestCount {
+    quests: i32,
 }
 
-pub async fn get_quest_count() -> anyhow::Result<i32> {
-    let url = "https://api.wynncraft.com/v3/map/quests";
+impl WynnClient {
+    pub async fn get_map_markers(&self) -> Result<Vec<Marker>> {
+        self.get(Endpoint::new(["map", "locations", "markers"]))
+            .await
+    }
 
-    let response = reqwest::get(url)
-        .await
-        .context("Failed to make the API request")?;
+    /// Needs an API key, without one the API answers with `WynnError::Unauthorized`.
+    pub async fn get_player_locations(&self) -> Result<Vec<PlayerLocation>> {
+        self.get(Endpoint::new(["map", "player"])).await
+    }
 
-    let data: Value = response
-        .json()
-        .await
-        .context("Failed to parse the JSON response")?;
+    pub async fn get_quest_count(&self) -> Result<i32> {
+        let count: QuestCount = self.get(Endpoint::new(["map", "quests"])).await?;
 
-    let quests = data["quests"]
-        .as_i64()
-        .ok_or_else(|| anyhow::Error::msg("Expected 'quests' to be an integer"))?
-        as i32;
+        Ok(count.quests)
+    }
+}
+
+pub async fn get_map_markers() -> Result<Vec<Marker>> {
+    default_client()?.get_map_markers().await
+}
+
+pub async fn get_player_locations() -> Result<Vec<PlayerLocation>> {
+    default_client()?.get_player_locations().await
+}
 
-    Ok(quests)
+pub async fn get_quest_count() -> Result<i32> {
+    default_client()?.get_quest_count().await
 }
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use reqwest::header::HeaderMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub limit: u32,
+    pub remaining: u32,
+    pub reset_at: Instant,
+}
+
+impl RateLimit {
+    pub fn reset_in(&self) -> Duration {
+        self.reset_at.saturating_duration_since(Instant::now())
+    }
+}
+
+// Budgets are tracked per bucket, which is the first path segment of the endpoint (`player`,
+// `guild`, `leaderboards`, ...) as that is how the API groups its limits.
+#[derive(Debug, Default)]
+pub(crate) struct RateLimiter {
+    buckets: Mutex<HashMap<String, RateLimit>>,
+    wait: bool,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(wait: bool) -> Self {
+        RateLimiter {
+            buckets: Mutex::new(HashMap::new()),
+            wait,
+        }
+    }
+
+    pub(crate) fn bucket(path: &str) -> String {
+        path.trim_start_matches('/')
+            .split(['/', '?'])
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    pub(crate) fn get(&self, bucket: &str) -> Option<RateLimit> {
+        self.buckets.lock().unwrap().get(bucket).copied()
+    }
+
+    pub(crate) fn all(&self) -> HashMap<String, RateLimit> {
+        self.buckets.lock().unwrap().clone()
+    }
+
+    // Takes one request from the bucket's budget. When waiting is enabled and the budget is spent
+    // this sleeps until the bucket resets instead of sending a request that would be rejected.
+    pub(crate) async fn acquire(&self, bucket: &str) {
+        loop {
+            let wait_for = {
+                let mut buckets = self.buckets.lock().unwrap();
+                match buckets.get_mut(bucket) {
+                    Some(limit) if limit.reset_at <= Instant::now() => {
+                        buckets.remove(bucket);
+                        None
+                    }
+                    Some(limit) if limit.remaining > 0 => {
+                        limit.remaining -= 1;
+                        None
+                    }
+                    Some(limit) if self.wait => Some(limit.reset_in()),
+                    _ => None,
+                }
+            };
+
+            match wait_for {
+                Some(duration) => tokio::time::sleep(duration).await,
+                None => return,
+            }
+        }
+    }
+
+    pub(crate) fn update(&self, bucket: &str, headers: &HeaderMap) {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+
+        if let (Some(limit), Some(remaining), Some(reset_at)) = (
+            header("ratelimit-limit"),
+            header("ratelimit-remaining"),
+            header("ratelimit-reset")
+                .and_then(|reset| Instant::now().checked_add(Duration::from_secs(reset))),
+        ) {
+            self.buckets.lock().unwrap().insert(
+                bucket.to_string(),
+                RateLimit {
+                    limit: u32::try_from(limit).unwrap_or(u32::MAX),
+                    remaining: u32::try_from(remaining).unwrap_or(u32::MAX),
+                    reset_at,
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use crate::error::WynnError;
+
+/// Controls how the client retries failed requests.
+///
+/// `max_attempts` counts the first request, so a value of 1 disables retries. The first retry
+/// waits `base_delay` and each one after waits twice as long, so retry `n` waits
+/// `base_delay * 2^(n - 1)`, capped at `max_delay`.
+///
+/// `RetryPolicy::default()` makes up to three attempts, but clients only retry when given a
+/// policy with `retry_policy`, their own default is `RetryPolicy::none()`.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub jitter: bool,
+    pub respect_retry_after: bool,
+    pub retry_statuses: Vec<u16>,
+    pub retry_transport_errors: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            respect_retry_after: true,
+            retry_statuses: vec![429, 500, 502, 503, 504],
+            retry_transport_errors: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        }
+    }
+
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn respect_retry_after(mut self, respect_retry_after: bool) -> Self {
+        self.respect_retry_after = respect_retry_after;
+        self
+    }
+
+    pub fn retry_statuses(mut self, retry_statuses: Vec<u16>) -> Self {
+        self.retry_statuses = retry_statuses;
+        self
+    }
+
+    pub fn retry_transport_errors(mut self, retry_transport_errors: bool) -> Self {
+        self.retry_transport_errors = retry_transport_errors;
+        self
+    }
+
+    pub(crate) fn should_retry(&self, error: &WynnError) -> bool {
+        match error {
+            WynnError::Transport(error) if error.status().is_none() => {
+                self.retry_transport_errors
+                    && (error.is_timeout() || error.is_connect() || error.is_request())
+            }
+            WynnError::MultipleChoices(_)
+            | WynnError::Decode { .. }
+            | WynnError::Config(_)
+            | WynnError::Offline { .. }
+            | WynnError::Io(_) => false,
+            _ => error
+                .status()
+                .is_some_and(|status| self.retry_statuses.contains(&status)),
+        }
+    }
+
+    // `attempt` is the number of attempts made so far, starting at 1.
+    pub(crate) fn delay(&self, attempt: u32, error: &WynnError) -> Duration {
+        if self.respect_retry_after {
+            if let WynnError::RateLimited {
+                retry_after: Some(retry_after),
+            } = error
+            {
+                return (*retry_after).min(self.max_delay);
+            }
+        }
+
+        let exponent = attempt.saturating_sub(1).min(16);
+        let delay = self
+            .base_delay
+            .saturating_mul(1 << exponent)
+            .min(self.max_delay);
+
+        if self.jitter {
+            // Keep at least half of the delay so retries never bunch up at zero.
+            let half = delay / 2;
+            half + half.mul_f64(fastrand::f64())
+        } else {
+            delay
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use crate::client::{default_client, WynnClient};
+use crate::endpoint::Endpoint;
+use crate::error::Result;
+use crate::guild::GuildChoice;
+use crate::player::PlayerChoice;
+use crate::Location;
+use serde::Deserialize;
+
+/// Everything matching a search, player and guild hits can be passed to `resolve_player_choice`
+/// and `resolve_guild_choice`.
+#[derive(Debug, Deserialize)]
+#[serde(from = "RawSearchResults")]
+pub struct SearchResults {
+    pub query: String,
+    pub players: Vec<PlayerChoice>,
+    pub guilds: Vec<GuildChoice>,
+    // Guilds whose prefix matched rather than their name.
+    pub guild_prefixes: Vec<GuildChoice>,
+    pub territories: Vec<SearchArea>,
+    pub discoveries: Vec<SearchArea>,
+}
+
+#[derive(Debug)]
+pub struct SearchArea {
+    pub name: String,
+    pub start_location: Location,
+    pub end_location: Location,
+}
+
+impl SearchResults {
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+            && self.guilds.is_empty()
+            && self.guild_prefixes.is_empty()
+            && self.territories.is_empty()
+            && self.discoveries.is_empty()
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct RawSearchResults {
+    query: String,
+    players: BTreeMap<String, String>,
+    guilds: BTreeMap<String, RawGuildHit>,
+    guilds_prefix: BTreeMap<String, RawGuildHit>,
+    territories: BTreeMap<String, RawArea>,
+    discoveries: BTreeMap<String, RawArea>,
+}
+
+#[derive(Deserialize)]
+struct RawGuildHit {
+    name: String,
+    prefix: String,
+}
+
+#[derive(Deserialize)]
+struct RawArea {
+    start: Location,
+    end: Location,
+}
+
+fn guild_hits(hits: BTreeMap<String, RawGuildHit>) -> Vec<GuildChoice> {
+    hits.into_iter()
+        .map(|(uuid, hit)| GuildChoice {
+            uuid,
+            name: hit.name,
+            prefix: hit.prefix,
+        })
+        .collect()
+}
+
+fn areas(areas: BTreeMap<String, RawArea>) -> Vec<SearchArea> {
+    areas
+        .into_iter()
+        .map(|(name, area)| SearchArea {
+            name,
+            start_location: area.start,
+            end_location: area.end,
+        })
+        .collect()
+}
+
+impl From<RawSearchResults> for SearchResults {
+    fn from(raw: RawSearchResults) -> Self {
+        SearchResults {
+            query: raw.query,
+            players: raw
+                .players
+                .into_iter()
+                .map(|(uuid, name)| PlayerChoice {
+                    uuid,
+                    stored_name: name,
+                    rank: None,
+                    prefix: None,
+                })
+                .collect(),
+            guilds: guild_hits(raw.guilds),
+            guild_prefixes: guild_hits(raw.guilds_prefix),
+            territories: areas(raw.territories),
+            discoveries: areas(raw.discoveries),
+        }
+    }
+}
+
+impl WynnClient {
+    pub async fn search(&self, query: &str) -> Result<SearchResults> {
+        self.get(Endpoint::new(["search", query])).await
+    }
+}
+
+pub async fn search(query: &str) -> Result<SearchResults> {
+    default_client()?.search(query).await
+}
@@ -0,0 +1,179 @@
+use std::fmt;
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// A date and time sent by the API, such as `2024-04-28T15:44:40.560Z`.
+///
+/// The string is parsed once when it is decoded and kept next to the result, so a date that isn't
+/// RFC 3339 still has its original text and gives `None` from every time accessor. With the
+/// `chrono` feature chrono does the parsing, without it only the `2024-04-28T15:44:40.560Z` form
+/// with a `Z` or `±HH:MM` offset is read.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(from = "String")]
+pub struct Timestamp {
+    raw: String,
+    time: Option<SystemTime>,
+}
+
+impl Timestamp {
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        self.time
+    }
+
+    // The same instant as `to_system_time`, so both accept exactly the same strings.
+    #[cfg(feature = "chrono")]
+    pub fn to_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.time.map(chrono::DateTime::from)
+    }
+
+    /// Time between this timestamp and `now`, `None` if it can't be parsed or is after `now`.
+    pub fn elapsed_since(&self, now: SystemTime) -> Option<Duration> {
+        now.duration_since(self.to_system_time()?).ok()
+    }
+
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.elapsed_since(SystemTime::now())
+    }
+
+    /// Whole days since this timestamp.
+    pub fn days_since(&self) -> Option<u64> {
+        self.elapsed()
+            .map(|elapsed| elapsed.as_secs() / SECONDS_PER_DAY)
+    }
+}
+
+impl From<String> for Timestamp {
+    fn from(raw: String) -> Self {
+        let time = parse_rfc3339(&raw);
+        Timestamp { raw, time }
+    }
+}
+
+impl From<&str> for Timestamp {
+    fn from(raw: &str) -> Self {
+        Timestamp::from(raw.to_string())
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl PartialEq<str> for Timestamp {
+    fn eq(&self, other: &str) -> bool {
+        self.raw == other
+    }
+}
+
+impl PartialEq<&str> for Timestamp {
+    fn eq(&self, other: &&str) -> bool {
+        self.raw == *other
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn parse_rfc3339(raw: &str) -> Option<SystemTime> {
+    chrono::DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(SystemTime::from)
+}
+
+// `YYYY-MM-DDTHH:MM:SS[.fraction]` followed by `Z` or `±HH:MM`, anything else is rejected.
+#[cfg(not(feature = "chrono"))]
+fn parse_rfc3339(raw: &str) -> Option<SystemTime> {
+    use std::time::UNIX_EPOCH;
+
+    let number = |digits: &str, width: usize, max: i64| -> Option<i64> {
+        if digits.len() != width || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse().ok().filter(|number| *number <= max)
+    };
+
+    let (datetime, offset) = match raw.strip_suffix('Z') {
+        Some(datetime) => (datetime, 0),
+        None => {
+            let (datetime, offset) = raw.split_at_checked(raw.len().checked_sub(6)?)?;
+            let sign = match offset.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            let (hours, minutes) = offset[1..].split_once(':')?;
+            let offset = number(hours, 2, 23)? * 3600 + number(minutes, 2, 59)? * 60;
+            (datetime, sign * offset)
+        }
+    };
+
+    let (date, time) = datetime.split_once('T')?;
+    let (time, fraction) = match time.split_once('.') {
+        Some((time, fraction)) => (time, Some(fraction)),
+        None => (time, None),
+    };
+    let date: Vec<&str> = date.split('-').collect();
+    let time: Vec<&str> = time.split(':').collect();
+    let ([year, month, day], [hour, minute, second]) = (date.as_slice(), time.as_slice()) else {
+        return None;
+    };
+    let (year, month) = (number(year, 4, 9999)?, number(month, 2, 12)?);
+    let day = number(day, 2, days_in_month(year, month))?;
+    if month == 0 || day == 0 {
+        return None;
+    }
+    let seconds = days_from_civil(year, month, day) * 86400
+        + number(hour, 2, 23)? * 3600
+        + number(minute, 2, 59)? * 60
+        + number(second, 2, 59)?
+        - offset;
+
+    // Anything past nanoseconds is dropped rather than rounded.
+    let nanos = match fraction {
+        Some(fraction) if !fraction.is_empty() && fraction.bytes().all(|b| b.is_ascii_digit()) => {
+            format!("{:0<9}", &fraction[..fraction.len().min(9)])
+                .parse()
+                .ok()?
+        }
+        Some(_) => return None,
+        None => 0,
+    };
+    let nanos = Duration::from_nanos(nanos);
+
+    if seconds >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_secs(seconds.unsigned_abs()) + nanos)
+    } else {
+        UNIX_EPOCH
+            .checked_sub(Duration::from_secs(seconds.unsigned_abs()))?
+            .checked_add(nanos)
+    }
+}
+
+#[cfg(not(feature = "chrono"))]
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Days since 1970-01-01 in the proleptic Gregorian calendar.
+#[cfg(not(feature = "chrono"))]
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
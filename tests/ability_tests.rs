@@ -0,0 +1,177 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockResponse, MockServer};
+    use antikythera_rs::*;
+
+    const TREE: &str = r#"{
+        "archetypes": {
+            "Boltslinger": {"name": "Boltslinger", "description": "Rain arrows", "slot": 1},
+            "Sharpshooter": {"name": "Sharpshooter", "description": "Precise shots", "slot": 2}
+        },
+        "pages": {
+            "1": {
+                "arrowBomb": {"type": "ability", "name": "Arrow Bomb",
+                    "coordinates": {"x": 4, "y": 1}, "requirements": {"ABILITY_POINTS": 1},
+                    "links": ["arrowStorm", "focus"]},
+                "connector_1": {"type": "connector", "coordinates": {"x": 4, "y": 2}},
+                "arrowStorm": {"type": "ability", "name": "Arrow Storm", "archetype": "Boltslinger",
+                    "coordinates": {"x": 2, "y": 3}, "requirements": {"ABILITY_POINTS": 2},
+                    "links": ["hailstorm"], "locks": ["focus"]},
+                "focus": {"type": "ability", "name": "Focus", "archetype": "Sharpshooter",
+                    "coordinates": {"x": 6, "y": 3}, "requirements": {"ABILITY_POINTS": 2}}
+            },
+            "2": {
+                "hailstorm": {"type": "ability", "name": "Hailstorm", "archetype": "Boltslinger",
+                    "coordinates": {"x": 2, "y": 1},
+                    "requirements": {"ABILITY_POINTS": 3, "NODE": "arrowStorm",
+                        "ARCHETYPE": {"name": "Boltslinger", "amount": 1}}}
+            }
+        }
+    }"#;
+
+    #[tokio::test]
+    async fn test_get_ability_tree() {
+        match get_ability_tree("archer").await {
+            Ok(tree) => {
+                println!("Archer Ability Tree: {:?}", tree);
+            }
+            Err(e) => {
+                println!("Error: {:?}", e);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ability_tree_graph() {
+        let server = MockServer::start().await;
+        server.mock("/ability/tree/archer", MockResponse::json(200, TREE));
+        server.mock(
+            "/classes/archer",
+            MockResponse::json(
+                200,
+                r#"{"id": "archer", "name": "Archer", "lore": "", "overallDifficulty": 2,
+                    "archetypes": {"boltslinger": {"name": "Boltslinger", "damage": 3},
+                        "sharpshooter": {"name": "Sharpshooter", "damage": 4}}}"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let tree = client.get_ability_tree("archer").await.unwrap();
+        assert_eq!(tree.class, "archer");
+        assert_eq!(tree.nodes.len(), 4);
+        assert_eq!(tree.connectors.len(), 1);
+        assert_eq!(tree.connectors[0].coordinates, Coordinates { x: 4, y: 2 });
+
+        let hailstorm = tree.node("hailstorm").unwrap();
+        assert_eq!(hailstorm.page, 2);
+        assert_eq!(hailstorm.cost, 3);
+        assert_eq!(hailstorm.parents, vec!["arrowStorm"]);
+        assert_eq!(hailstorm.required_ability.as_deref(), Some("arrowStorm"));
+        assert_eq!(
+            hailstorm.required_archetype,
+            Some(ArchetypeRequirement {
+                archetype: "boltslinger".to_string(),
+                points: 1
+            })
+        );
+
+        let ancestors: Vec<&str> = tree
+            .ancestors("hailstorm")
+            .iter()
+            .map(|node| node.id.as_str())
+            .collect();
+        assert_eq!(ancestors, vec!["arrowStorm", "arrowBomb"]);
+
+        let bolt: Vec<&str> = tree
+            .archetype_nodes("Boltslinger")
+            .iter()
+            .map(|node| node.id.as_str())
+            .collect();
+        assert_eq!(bolt, vec!["arrowStorm", "hailstorm"]);
+
+        let ids = |nodes: Vec<&AbilityNode>| -> Vec<String> {
+            nodes.iter().map(|node| node.id.clone()).collect()
+        };
+        assert_eq!(ids(tree.reachable(&[])), vec!["arrowBomb"]);
+        assert_eq!(
+            ids(tree.reachable(&["arrowBomb"])),
+            vec!["arrowStorm", "focus"]
+        );
+        // Arrow Storm blocks Focus.
+        assert_eq!(
+            ids(tree.reachable(&["arrowBomb", "arrowStorm"])),
+            vec!["hailstorm"]
+        );
+
+        let class = client.get_class("archer").await.unwrap();
+        let archetype = tree.archetype("boltslinger").unwrap();
+        assert_eq!(archetype.slot, 1);
+        assert_eq!(archetype.class_archetype(&class).unwrap().damage, 3);
+    }
+
+    #[tokio::test]
+    async fn test_ability_allocation() {
+        let server = MockServer::start().await;
+        server.mock("/ability/tree/archer", MockResponse::json(200, TREE));
+        server.mock(
+            "/player/Salted/characters/char-uuid/abilities",
+            MockResponse::json(
+                200,
+                r#"[{"type": "ability", "id": "arrowBomb", "coordinates": {"x": 4, "y": 1}},
+                    {"type": "connector", "id": "connector_1", "coordinates": {"x": 4, "y": 2}},
+                    {"type": "ability", "id": "arrowStorm", "coordinates": {"x": 2, "y": 3}}]"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let tree = client.get_ability_tree("archer").await.unwrap();
+        let map = client
+            .get_character_ability_map("Salted", "char-uuid")
+            .await
+            .unwrap();
+        assert_eq!(map.unlocked, vec!["arrowBomb", "arrowStorm"]);
+        assert_eq!(
+            tree.validate_allocation(&map.ids(), MAX_ABILITY_POINTS),
+            Ok(())
+        );
+        assert_eq!(
+            tree.validate_allocation(&["arrowBomb", "arrowStorm", "hailstorm"], 5),
+            Err(vec![AllocationError::OverBudget {
+                spent: 6,
+                budget: 5
+            }])
+        );
+        assert_eq!(
+            tree.validate_allocation(&["arrowBomb", "arrowStorm", "focus"], 45),
+            Err(vec![AllocationError::Blocked {
+                ability: "arrowStorm".to_string(),
+                blocked: "focus".to_string()
+            }])
+        );
+        assert_eq!(
+            tree.validate_allocation(&["arrowBomb", "hailstorm", "arrowMissile"], 45),
+            Err(vec![
+                AllocationError::UnknownAbility("arrowMissile".to_string()),
+                AllocationError::Disconnected("hailstorm".to_string()),
+                AllocationError::MissingAbility {
+                    ability: "hailstorm".to_string(),
+                    required: "arrowStorm".to_string()
+                },
+                AllocationError::ArchetypeMinimum {
+                    ability: "hailstorm".to_string(),
+                    archetype: "boltslinger".to_string(),
+                    required: 1,
+                    unlocked: 0
+                },
+            ])
+        );
+    }
+}
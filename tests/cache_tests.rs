@@ -0,0 +1,142 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::common::{MockResponse, MockServer};
+    use antikythera_rs::*;
+
+    const CLASSES: &str = r#"{"archer": {"name": "Archer", "overallDifficulty": 2}}"#;
+
+    fn client_for(server: &MockServer, cache: CacheConfig) -> WynnClient {
+        WynnClient::builder()
+            .base_url(server.base_url())
+            .cache(cache)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cache_honours_max_age() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/classes",
+            MockResponse::json(200, CLASSES).header("Cache-Control", "public, max-age=3600"),
+        );
+        let client = client_for(&server, CacheConfig::default());
+
+        client.get_classes().await.unwrap();
+        client.get_classes().await.unwrap();
+
+        assert_eq!(server.request_count(), 1);
+        assert_eq!(client.cached_responses(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cache_skips_no_store_and_uncacheable() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/classes",
+            MockResponse::json(200, CLASSES).header("Cache-Control", "no-store, max-age=60"),
+        );
+        server.mock("/map/quests", MockResponse::json(200, r#"{"quests": 262}"#));
+        let client = client_for(&server, CacheConfig::default());
+
+        client.get_classes().await.unwrap();
+        client.get_classes().await.unwrap();
+        client.get_quest_count().await.unwrap();
+        client.get_quest_count().await.unwrap();
+
+        assert_eq!(server.request_count(), 4);
+        assert_eq!(client.cached_responses(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cache_honours_expires() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/classes",
+            MockResponse::json(200, CLASSES)
+                .header("Date", "Sun, 18 Oct 2026 12:00:00 GMT")
+                .header("Expires", "Sun, 18 Oct 2026 12:00:30 GMT"),
+        );
+        let client = client_for(&server, CacheConfig::default());
+
+        client.get_classes().await.unwrap();
+        client.get_classes().await.unwrap();
+
+        assert_eq!(server.request_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cache_ttl_override_and_invalidate() {
+        let server = MockServer::start().await;
+        server.mock("/map/quests", MockResponse::json(200, r#"{"quests": 262}"#));
+        server.mock(
+            "/classes",
+            MockResponse::json(200, CLASSES).header("Cache-Control", "max-age=3600"),
+        );
+        let client = client_for(
+            &server,
+            CacheConfig::default()
+                .ttl_override("/map", Duration::from_secs(60))
+                .ttl_override("/classes", Duration::ZERO),
+        );
+
+        client.get_quest_count().await.unwrap();
+        client.get_quest_count().await.unwrap();
+        client.get_classes().await.unwrap();
+        client.get_classes().await.unwrap();
+        assert_eq!(server.request_count(), 3);
+
+        client.invalidate("/map");
+        client.get_quest_count().await.unwrap();
+        assert_eq!(server.request_count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_ttl_overrides_are_encoded_like_requests() {
+        let server = MockServer::start().await;
+        server.mock("/item/quick/Sky%20Ring", MockResponse::json(200, "{}"));
+        let client = client_for(
+            &server,
+            CacheConfig::default().ttl_override("/item/quick/Sky Ring", Duration::from_secs(60)),
+        );
+
+        client.quick_search_items("Sky Ring").await.unwrap();
+        client.quick_search_items("Sky Ring").await.unwrap();
+        assert_eq!(server.request_count(), 1);
+
+        client.invalidate("/item/quick/Sky Ring");
+        client.quick_search_items("Sky Ring").await.unwrap();
+        assert_eq!(server.request_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cache_size_limit_and_expiry() {
+        let server = MockServer::start().await;
+        server.mock("/map/quests", MockResponse::json(200, r#"{"quests": 262}"#));
+        server.mock("/classes", MockResponse::json(200, CLASSES));
+        let client = client_for(
+            &server,
+            CacheConfig::default()
+                .max_entries(1)
+                .default_ttl(Duration::from_millis(200)),
+        );
+
+        client.get_quest_count().await.unwrap();
+        client.get_classes().await.unwrap();
+        assert_eq!(client.cached_responses(), 1);
+
+        client.get_classes().await.unwrap();
+        assert_eq!(server.request_count(), 2);
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        client.get_classes().await.unwrap();
+        assert_eq!(server.request_count(), 3);
+
+        client.clear_cache();
+        assert_eq!(client.cached_responses(), 0);
+    }
+}
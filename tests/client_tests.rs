@@ -0,0 +1,91 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockResponse, MockServer};
+    use antikythera_rs::*;
+    use reqwest::header::{HeaderName, HeaderValue};
+
+    #[tokio::test]
+    async fn test_client_uses_base_url_and_headers() {
+        let server = MockServer::start().await;
+        server.mock("/map/quests", MockResponse::json(200, r#"{"quests": 262}"#));
+
+        let client = WynnClient::builder()
+            .base_url(format!("{}/", server.base_url()))
+            .user_agent("antikythera-tests")
+            .default_header(
+                HeaderName::from_static("x-test"),
+                HeaderValue::from_static("yes"),
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(client.get_quest_count().await.unwrap(), 262);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].target, "/map/quests");
+        assert_eq!(requests[0].headers["user-agent"], "antikythera-tests");
+        assert_eq!(requests[0].headers["x-test"], "yes");
+    }
+
+    #[tokio::test]
+    async fn test_client_default_base_url() {
+        let client = WynnClient::new();
+        assert_eq!(client.base_url(), DEFAULT_BASE_URL);
+    }
+
+    #[tokio::test]
+    async fn test_api_key_is_sent_as_bearer_token() {
+        let server = MockServer::start().await;
+        server.mock("/map/quests", MockResponse::json(200, r#"{"quests": 262}"#));
+
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .api_key("secret-key")
+            .build()
+            .unwrap();
+
+        assert!(client.is_authenticated());
+        client.get_quest_count().await.unwrap();
+        assert_eq!(
+            server.requests()[0].headers["authorization"],
+            "Bearer secret-key"
+        );
+        assert!(!format!("{:?}", client).contains("secret-key"));
+    }
+
+    #[tokio::test]
+    async fn test_api_key_from_env() {
+        std::env::set_var(API_KEY_ENV, "env-key");
+        let client = WynnClient::builder().api_key_from_env().build().unwrap();
+        std::env::remove_var(API_KEY_ENV);
+
+        assert!(client.is_authenticated());
+        assert!(!WynnClient::new().is_authenticated());
+    }
+
+    #[tokio::test]
+    async fn test_rejected_api_key() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/map/quests",
+            MockResponse::json(401, r#"{"Error": "Invalid API key"}"#),
+        );
+
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .api_key("wrong-key")
+            .build()
+            .unwrap();
+
+        match client.get_quest_count().await {
+            Err(WynnError::Unauthorized { status, message }) => {
+                assert_eq!(status, 401);
+                assert_eq!(message.as_deref(), Some("Invalid API key"));
+            }
+            other => panic!("Expected Unauthorized, got {:?}", other),
+        }
+    }
+}
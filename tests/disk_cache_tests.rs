@@ -0,0 +1,166 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::SystemTime;
+
+    use crate::common::{MockResponse, MockServer};
+    use antikythera_rs::*;
+
+    fn cache_directory(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("antikythera-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        directory
+    }
+
+    #[tokio::test]
+    async fn test_disk_cache_survives_restart() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/map/quests",
+            MockResponse::json(200, r#"{"quests": 262}"#).header("Cache-Control", "max-age=600"),
+        );
+        let directory = cache_directory("restart");
+
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .disk_cache(&directory)
+            .build()
+            .unwrap();
+        assert_eq!(client.get_quest_count().await.unwrap(), 262);
+
+        let restarted = WynnClient::builder()
+            .base_url(server.base_url())
+            .disk_cache(&directory)
+            .build()
+            .unwrap();
+        assert_eq!(restarted.get_quest_count().await.unwrap(), 262);
+        assert_eq!(server.request_count(), 1);
+
+        let stored = restarted.stored_response("/map/quests").await.unwrap();
+        assert!(stored.is_fresh());
+        assert!(stored.fetched_at <= SystemTime::now());
+
+        restarted.invalidate_stored("/map").await.unwrap();
+        assert!(restarted.stored_response("/map/quests").await.is_none());
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+
+    #[tokio::test]
+    async fn test_stale_entries_are_refetched_online() {
+        let server = MockServer::start().await;
+        server.mock("/map/quests", MockResponse::json(200, r#"{"quests": 262}"#));
+        let directory = cache_directory("stale");
+
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .disk_cache(&directory)
+            .build()
+            .unwrap();
+        client.get_quest_count().await.unwrap();
+        client.get_quest_count().await.unwrap();
+
+        assert_eq!(server.request_count(), 2);
+        assert!(!client
+            .stored_response("/map/quests")
+            .await
+            .unwrap()
+            .is_fresh());
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+
+    #[tokio::test]
+    async fn test_offline_mode() {
+        let server = MockServer::start().await;
+        server.mock("/map/quests", MockResponse::json(200, r#"{"quests": 262}"#));
+        let directory = cache_directory("offline");
+
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .disk_cache(&directory)
+            .build()
+            .unwrap();
+        client.get_quest_count().await.unwrap();
+
+        let offline = WynnClient::builder()
+            .base_url(server.base_url())
+            .disk_cache(&directory)
+            .offline(true)
+            .build()
+            .unwrap();
+        assert!(offline.is_offline());
+        assert_eq!(offline.get_quest_count().await.unwrap(), 262);
+
+        match offline.get_classes().await {
+            Err(WynnError::Offline { url }) => assert!(url.ends_with("/classes")),
+            other => panic!("Expected Offline, got {:?}", other),
+        }
+        assert_eq!(server.request_count(), 1);
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_leaves_foreign_files() {
+        let server = MockServer::start().await;
+        server.mock("/map/quests", MockResponse::json(200, r#"{"quests": 262}"#));
+        let directory = cache_directory("foreign");
+        std::fs::create_dir_all(&directory).unwrap();
+        let notes = directory.join("notes.json");
+        std::fs::write(&notes, r#"{"todo": "keep me"}"#).unwrap();
+        let lookalike = directory.join("00000000deadbeef.json");
+        std::fs::write(&lookalike, "not a cache entry").unwrap();
+
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .disk_cache(&directory)
+            .build()
+            .unwrap();
+        client.get_quest_count().await.unwrap();
+
+        client.invalidate_stored("/").await.unwrap();
+        assert!(client.stored_response("/map/quests").await.is_none());
+        assert!(notes.exists());
+        assert!(lookalike.exists());
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+
+    #[tokio::test]
+    async fn test_stored_paths_are_encoded_like_requests() {
+        let server = MockServer::start().await;
+        let guild = r#"{"uuid": "g", "name": "Salt Mines", "prefix": "SALT", "level": 1,
+            "xpPercent": 0, "territories": 0, "wars": 0, "created": "", "online": 0,
+            "members": {"total": 0}, "seasonRanks": {}}"#;
+        server.mock("/guild/Salt%20Mines", MockResponse::json(200, guild));
+        let directory = cache_directory("encoded");
+
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .disk_cache(&directory)
+            .build()
+            .unwrap();
+        client
+            .get_guild(&GuildIdentifier::name("Salt Mines").unwrap())
+            .await
+            .unwrap();
+
+        let stored = client
+            .stored_response("/guild/Salt Mines?identifier=uuid")
+            .await
+            .unwrap();
+        assert!(stored.url.ends_with("/guild/Salt%20Mines?identifier=uuid"));
+
+        client.invalidate_stored("/guild/Salt M").await.unwrap();
+        assert!(client
+            .stored_response("/guild/Salt Mines?identifier=uuid")
+            .await
+            .is_none());
+
+        let _ = std::fs::remove_dir_all(&directory);
+    }
+}
@@ -0,0 +1,132 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::common::{MockResponse, MockServer};
+    use antikythera_rs::*;
+
+    async fn client_for(server: &MockServer) -> WynnClient {
+        WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_not_found() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/player/NoSuchPlayer",
+            MockResponse::json(404, r#"{"Error": "Player not found"}"#),
+        );
+        let client = client_for(&server).await;
+
+        match client.get_player_main_stats("NoSuchPlayer").await {
+            Err(WynnError::NotFound) => {}
+            other => panic!("Expected NotFound, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/map/quests",
+            MockResponse::json(429, "{}").header("Retry-After", "12"),
+        );
+        let client = client_for(&server).await;
+
+        match client.get_quest_count().await {
+            Err(WynnError::RateLimited { retry_after }) => {
+                assert_eq!(retry_after, Some(Duration::from_secs(12)));
+            }
+            other => panic!("Expected RateLimited, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_status_classes() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/classes/archer",
+            MockResponse::json(400, r#"{"Error": "Bad class"}"#),
+        );
+        server.mock("/classes", MockResponse::json(503, ""));
+        let client = client_for(&server).await;
+
+        match client.get_class("archer").await {
+            Err(WynnError::ClientError { status, message }) => {
+                assert_eq!(status, 400);
+                assert_eq!(message.as_deref(), Some("Bad class"));
+            }
+            other => panic!("Expected ClientError, got {:?}", other),
+        }
+
+        match client.get_classes().await {
+            Err(error @ WynnError::ServerError { .. }) => assert_eq!(error.status(), Some(503)),
+            other => panic!("Expected ServerError, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decode_error_reports_path() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/map/quests",
+            MockResponse::json(200, r#"{"quests": "many"}"#),
+        );
+        server.mock("/latest-news", MockResponse::json(200, "not json"));
+        let client = client_for(&server).await;
+
+        match client.get_quest_count().await {
+            Err(WynnError::Decode { field, path, .. }) => {
+                assert_eq!(field, "quests");
+                assert_eq!(path, "quests");
+            }
+            other => panic!("Expected Decode, got {:?}", other),
+        }
+
+        match client.get_latest_news().await {
+            Err(WynnError::Decode { path, .. }) => assert!(path.is_empty()),
+            other => panic!("Expected Decode, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decode_error_reports_nested_path() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/classes/archer",
+            MockResponse::json(
+                200,
+                r#"{"id": "archer", "name": "Archer", "lore": "", "overallDifficulty": "hard", "archetypes": {}}"#,
+            ),
+        );
+        server.mock(
+            "/map/locations/markers",
+            MockResponse::json(
+                200,
+                r#"[{"name": "Ragni", "x": "1", "y": "2", "z": "north"}]"#,
+            ),
+        );
+        let client = client_for(&server).await;
+
+        match client.get_class("archer").await {
+            Err(WynnError::Decode { field, path, .. }) => {
+                assert_eq!(field, "overallDifficulty");
+                assert_eq!(path, "overallDifficulty");
+            }
+            other => panic!("Expected Decode, got {:?}", other),
+        }
+
+        match client.get_map_markers().await {
+            Err(WynnError::Decode { field, path, .. }) => {
+                assert_eq!(field, "z");
+                assert_eq!(path, "[0].z");
+            }
+            other => panic!("Expected Decode, got {:?}", other),
+        }
+    }
+}
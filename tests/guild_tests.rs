@@ -1,5 +1,8 @@
+mod common;
+
 #[cfg(test)]
 mod tests {
+    use crate::common::{MockResponse, MockServer};
     use antikythera_rs::*;
 
     #[tokio::test]
@@ -55,8 +58,8 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_get_guild_from_name_username() {
-        match get_guild_from_name("Chiefs Of Corkus", false).await {
+    async fn test_get_guild_from_name() {
+        match get_guild_from_name("Chiefs Of Corkus").await {
             Ok(guild_data) => {
                 println!("Guild Data: {:?}", guild_data);
             }
@@ -67,8 +70,8 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_get_guild_from_name_uuid() {
-        match get_guild_from_name("Chiefs Of Corkus", true).await {
+    async fn test_get_guild_from_prefix() {
+        match get_guild_from_prefix("HOC").await {
             Ok(guild_data) => {
                 println!("Guild Data: {:?}", guild_data);
             }
@@ -79,26 +82,93 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_get_guild_from_prefix_username() {
-        match get_guild_from_prefix("HOC", false).await {
-            Ok(guild_data) => {
-                println!("Guild Data: {:?}", guild_data);
-            }
-            Err(e) => {
-                println!("Error: {:?}", e);
-            }
-        }
+    async fn test_get_guild_from_prefix_multiple_choices() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/guild/prefix/AVO",
+            MockResponse::json(
+                300,
+                r#"{
+                    "a1b2c3d4-0000-4000-8000-000000000001": {"name": "Avicia", "prefix": "AVO"},
+                    "a1b2c3d4-0000-4000-8000-000000000002": {"name": "Avocado", "prefix": "AVO"}
+                }"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let choices = match client.get_guild_from_prefix("AVO").await {
+            Err(WynnError::MultipleChoices(MultipleChoices::Guilds(choices))) => choices,
+            other => panic!("Expected guild choices, got {:?}", other),
+        };
+
+        assert_eq!(choices.len(), 2);
+        let avicia = choices
+            .iter()
+            .find(|choice| choice.name == "Avicia")
+            .unwrap();
+
+        let _ = client.resolve_guild_choice(avicia).await;
+        let requests = server.requests();
+        assert_eq!(
+            requests.last().unwrap().target,
+            "/guild/uuid/a1b2c3d4-0000-4000-8000-000000000001?identifier=uuid"
+        );
     }
 
     #[tokio::test]
-    async fn test_get_guild_from_prefix_uuid() {
-        match get_guild_from_prefix("HOC", true).await {
-            Ok(guild_data) => {
-                println!("Guild Data: {:?}", guild_data);
-            }
-            Err(e) => {
-                println!("Error: {:?}", e);
-            }
-        }
+    async fn test_decode_guild_and_territories() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/guild/TestGuild",
+            MockResponse::json(
+                200,
+                r#"{
+                    "uuid": "guild-uuid", "name": "Test Guild", "prefix": "TEST", "level": 80,
+                    "xpPercent": 12, "territories": 3, "wars": 100,
+                    "created": "2020-01-01T00:00:00.000Z", "online": 1,
+                    "members": {
+                        "total": 2,
+                        "owner": {"Owner": {"uuid": "owner-uuid", "online": true, "server": "WC1",
+                            "contributed": 1000, "contributionRank": 1, "joined": "2020-01-01"}},
+                        "recruit": {"Recruit": {"uuid": "recruit-uuid", "online": false, "server": null,
+                            "contributed": 0, "contributionRank": 2, "joined": "2021-01-01"}}
+                    },
+                    "banner": null,
+                    "seasonRanks": {"10": {"rating": 500, "finalTerritories": 2},
+                        "9": {"rating": 100, "finalTerritories": 0}}
+                }"#,
+            ),
+        );
+        server.mock(
+            "/guild/list/territory",
+            MockResponse::json(
+                200,
+                r#"{"Ragni": {"guild": {"uuid": "guild-uuid", "name": "Test Guild", "prefix": "TEST"},
+                    "acquired": "2024-01-01T00:00:00.000Z",
+                    "location": {"start": [1, 2], "end": [3, 4, 5]}}}"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let guild = client.get_guild_from_name("TestGuild").await.unwrap();
+        assert_eq!(guild.members.len(), 2);
+        assert_eq!(guild.members[0].username, "Owner");
+        assert_eq!(guild.members[0].uuid, "owner-uuid");
+        assert_eq!(guild.members[0].rank, "owner");
+        assert_eq!(guild.members[1].server, "");
+        assert!(guild.banner.layers.is_empty());
+        let seasons: Vec<i32> = guild.season_ranks.iter().map(|rank| rank.season).collect();
+        assert_eq!(seasons, vec![9, 10]);
+
+        let territories = client.get_territory_data().await.unwrap();
+        assert_eq!(territories.len(), 1);
+        assert_eq!(territories[0].territory_name, "Ragni");
+        assert_eq!(territories[0].owner.prefix, "TEST");
     }
 }
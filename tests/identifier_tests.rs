@@ -0,0 +1,128 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockResponse, MockServer};
+    use antikythera_rs::*;
+
+    const UUID: &str = "1ed075fb-a421-4b0d-8b5d-e5d7ef4b5d4e";
+
+    #[test]
+    fn test_player_identifiers() {
+        let username = PlayerIdentifier::parse("Salted_117").unwrap();
+        assert_eq!(username.kind(), PlayerIdentifierKind::Username);
+        assert_eq!(username.as_str(), "Salted_117");
+        for uuid in [
+            "1ed075fba4214b0d8b5de5d7ef4b5d4e",
+            "1ED075FB-A421-4B0D-8B5D-E5D7EF4B5D4E",
+            UUID,
+        ] {
+            let identifier = PlayerIdentifier::parse(uuid).unwrap();
+            assert_eq!(identifier.kind(), PlayerIdentifierKind::Uuid);
+            assert_eq!(identifier.as_str(), UUID);
+        }
+
+        for invalid in [
+            "",
+            "ab",
+            "seventeen_letters",
+            "has space",
+            "dash-ed",
+            "1ed075fb-a421",
+        ] {
+            match PlayerIdentifier::parse(invalid) {
+                Err(WynnError::InvalidIdentifier { kind, value }) => {
+                    assert_eq!((kind.as_str(), value.as_str()), ("username", invalid));
+                }
+                other => panic!("expected {} to be rejected, got {:?}", invalid, other),
+            }
+        }
+        assert!(PlayerIdentifier::uuid("Salted").is_err());
+        assert!(PlayerIdentifier::username(UUID).is_err());
+        assert_eq!(
+            "Salted".parse::<PlayerIdentifier>().unwrap().to_string(),
+            "Salted"
+        );
+    }
+
+    #[test]
+    fn test_guild_identifiers() {
+        let name = GuildIdentifier::name(" Salt Mines ").unwrap();
+        assert_eq!(name.kind(), GuildIdentifierKind::Name);
+        assert_eq!(name.as_str(), "Salt Mines");
+        assert!(GuildIdentifier::name("  ").is_err());
+        assert_eq!(GuildIdentifier::prefix("SALT").unwrap().as_str(), "SALT");
+        assert!(GuildIdentifier::prefix("SALTY").is_err());
+        assert!(GuildIdentifier::prefix("S4").is_err());
+        assert_eq!(
+            GuildIdentifier::uuid("1ed075fba4214b0d8b5de5d7ef4b5d4e").unwrap(),
+            GuildIdentifier::uuid(UUID).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_identifiers_drive_requests() {
+        let server = MockServer::start().await;
+        let player = r#"{"username": "Salted", "uuid": "1ed075fb-a421-4b0d-8b5d-e5d7ef4b5d4e"}"#;
+        server.mock("/player/Salted", MockResponse::json(200, player));
+        server.mock(
+            &format!("/player/{}", UUID),
+            MockResponse::json(200, player),
+        );
+        let guild = r#"{"uuid": "g", "name": "Salt Mines", "prefix": "SALT", "level": 1,
+            "xpPercent": 0, "territories": 0, "wars": 0, "created": "", "online": 0,
+            "members": {"total": 0}, "seasonRanks": {}}"#;
+        for route in [
+            "/guild/Salt Mines".replace(' ', "%20"),
+            "/guild/prefix/SALT".to_string(),
+            format!("/guild/uuid/{}", UUID),
+        ] {
+            server.mock(&route, MockResponse::json(200, guild));
+        }
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        client.get_player_main_stats("Salted").await.unwrap();
+        client
+            .get_player_main_stats("1ED075FBA4214B0D8B5DE5D7EF4B5D4E")
+            .await
+            .unwrap();
+        let identifier = PlayerIdentifier::uuid(UUID).unwrap();
+        client.get_player_main_stats(&identifier).await.unwrap();
+
+        // Invalid identifiers fail before anything is sent.
+        match client.get_player_main_stats("not a name").await {
+            Err(WynnError::InvalidIdentifier { kind, .. }) => assert_eq!(kind, "username"),
+            other => panic!("expected an invalid identifier, got {:?}", other),
+        }
+        assert_eq!(server.requests().len(), 3);
+
+        for identifier in [
+            GuildIdentifier::name("Salt Mines").unwrap(),
+            GuildIdentifier::prefix("SALT").unwrap(),
+            GuildIdentifier::uuid(UUID).unwrap(),
+        ] {
+            let guild = client.get_guild(&identifier).await.unwrap();
+            assert_eq!(guild.prefix, "SALT");
+        }
+
+        let targets: Vec<String> = server
+            .requests()
+            .iter()
+            .map(|request| request.target.clone())
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                "/player/Salted".to_string(),
+                format!("/player/{}", UUID),
+                format!("/player/{}", UUID),
+                "/guild/Salt%20Mines?identifier=uuid".to_string(),
+                "/guild/prefix/SALT?identifier=uuid".to_string(),
+                format!("/guild/uuid/{}?identifier=uuid", UUID),
+            ]
+        );
+    }
+}
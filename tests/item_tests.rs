@@ -0,0 +1,316 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockResponse, MockServer};
+    use antikythera_rs::*;
+    use futures::StreamExt;
+
+    fn page(current: i32, next: Option<i32>, items: &str) -> MockResponse {
+        let next = next.map_or("null".to_string(), |next| next.to_string());
+        MockResponse::json(
+            200,
+            &format!(
+                r#"{{"controller": {{"count": 3, "pages": 3, "prev": null, "current": {}, "next": {}}},
+                    "results": {{{}}}}}"#,
+                current, next, items
+            ),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_get_item_database_page() {
+        match get_item_database_page(1).await {
+            Ok(page) => {
+                println!("Item Database Page 1: {:?}", page);
+            }
+            Err(e) => {
+                println!("Error: {:?}", e);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decode_items() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/item/database",
+            page(
+                1,
+                Some(2),
+                r#"
+                "Oak Wood Spear": {"internalName": "Oak Wood Spear", "type": "weapon",
+                    "weaponType": "spear", "attackSpeed": "slow", "averageDps": 7.5,
+                    "requirements": {"level": 1, "classRequirement": "warrior"},
+                    "base": {"baseDamage": {"min": 5, "max": 8, "raw": 6}},
+                    "identifications": {"rawStrength": 1, "walkSpeed": {"min": 2, "max": 9, "raw": 7}},
+                    "powderSlots": 1, "rarity": "common", "dropRestriction": "normal"},
+                "Bob's Tear": {"internalName": "Bob's Tear", "type": "ingredient", "tier": 3,
+                    "requirements": {"level": 75, "skills": ["armouring", "tailoring"]},
+                    "identifications": {"rawMainAttackDamage": {"min": 60, "max": 80, "raw": 70}}},
+                "Speedy Axe": {"type": "tool", "toolType": "axe", "gatheringSpeed": 30,
+                    "rarity": "shiny", "requirements": null}
+                "#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let page = client.get_item_database_page(1).await.unwrap();
+        assert_eq!(server.requests()[0].target, "/item/database?page=1");
+        assert_eq!((page.current, page.next, page.previous), (1, Some(2), None));
+        assert_eq!(page.items.len(), 3);
+
+        let tear = &page.items[0];
+        assert_eq!(tear.name, "Bob's Tear");
+        assert_eq!(tear.kind, ItemKind::Ingredient { tier: 3 });
+        assert_eq!(tear.requirements.skills, vec!["armouring", "tailoring"]);
+
+        let spear = &page.items[1];
+        assert_eq!(spear.name, "Oak Wood Spear");
+        assert_eq!(spear.kind.as_str(), "weapon");
+        assert_eq!(spear.rarity, Some(ItemRarity::Common));
+        assert_eq!(
+            spear.requirements.class_requirement.as_deref(),
+            Some("warrior")
+        );
+        assert_eq!(
+            spear.base["baseDamage"],
+            StatValue::Range {
+                min: 5,
+                max: 8,
+                raw: 6
+            }
+        );
+        assert_eq!(spear.identifications["rawStrength"], StatValue::Fixed(1));
+
+        let axe = &page.items[2];
+        assert_eq!(axe.internal_name, "Speedy Axe");
+        assert_eq!(axe.rarity, Some(ItemRarity::Unknown("shiny".to_string())));
+        assert_eq!(
+            axe.kind,
+            ItemKind::Tool {
+                tool_type: "axe".to_string(),
+                gathering_speed: Some(30)
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_item_database_stream() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/item/database",
+            page(1, Some(2), r#""A": {"type": "charm"}"#),
+        );
+        server.mock(
+            "/item/database",
+            page(2, Some(3), r#""B": {"type": "charm"}"#),
+        );
+        server.mock(
+            "/item/database",
+            page(3, None, r#""C": {"type": "material", "tier": 1}"#),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let pages: Vec<ItemPage> = client
+            .item_database_pages()
+            .map(|page| page.unwrap())
+            .collect()
+            .await;
+        let names: Vec<&str> = pages
+            .iter()
+            .flat_map(|page| page.items.iter().map(|item| item.name.as_str()))
+            .collect();
+        assert_eq!(names, vec!["A", "B", "C"]);
+
+        let targets: Vec<String> = server
+            .requests()
+            .into_iter()
+            .map(|request| request.target)
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                "/item/database?page=1",
+                "/item/database?page=2",
+                "/item/database?page=3"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_full_item_database() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/item/database",
+            MockResponse::json(
+                200,
+                r#"{"Ring": {"type": "accessory", "accessoryType": "ring"}, "Tome": {"type": "tome", "tomeType": "guild_tome"}}"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let items = client.get_full_item_database().await.unwrap();
+        assert_eq!(
+            server.requests()[0].target,
+            "/item/database?fullResult=True"
+        );
+        assert_eq!(
+            items[0].kind,
+            ItemKind::Accessory {
+                accessory_type: "ring".to_string()
+            }
+        );
+        assert_eq!(items[1].kind.as_str(), "tome");
+    }
+
+    #[tokio::test]
+    async fn test_search_items() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/item/search",
+            page(
+                1,
+                None,
+                r#""Nirvana": {"type": "weapon", "weaponType": "wand", "attackSpeed": "slow",
+                    "rarity": "mythic", "requirements": {"level": 100, "classRequirement": "mage"}}"#,
+            ),
+        );
+        server.mock(
+            "/item/quick/Nirv",
+            MockResponse::json(
+                200,
+                r#"{"Nirvana": {"type": "weapon", "weaponType": "wand"}}"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let query = ItemQuery::new()
+            .item_type("wand")
+            .rarity(ItemRarity::Mythic)
+            .tier(3)
+            .level_range(95, 105)
+            .class_requirement("mage")
+            .identification("rawIntelligence")
+            .attack_speed("slow");
+        let page = client.search_items(&query).await.unwrap();
+        assert_eq!(page.items[0].name, "Nirvana");
+        assert_eq!(page.items[0].rarity, Some(ItemRarity::Mythic));
+
+        let request = &server.requests()[0];
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.target, "/item/search?page=1");
+        assert_eq!(request.headers["content-type"], "application/json");
+        let body: serde_json::Value = serde_json::from_str(&request.body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "type": ["wand"],
+                "tier": ["mythic", 3],
+                "levelRange": [95, 105],
+                "classRequirement": "mage",
+                "identifications": ["rawIntelligence"],
+                "attackSpeed": ["slow"],
+            })
+        );
+
+        let items = client.quick_search_items("Nirv").await.unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "Nirvana");
+        assert_eq!(server.requests()[1].method, "GET");
+    }
+
+    #[tokio::test]
+    async fn test_item_metadata() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/item/metadata",
+            MockResponse::json(
+                200,
+                r#"{"identifications": ["rawStrength", "walkSpeed", "manaSteal", "xpBonus"],
+                    "filters": {"type": ["weapon", "armour", "ingredient"],
+                        "advanced": {"weapon": ["wand", "spear"], "armour": ["helmet"]},
+                        "tier": {"items": ["common", "mythic"], "ingredients": [0, 1, 2, 3],
+                            "materials": [1, 2, 3]},
+                        "attackSpeed": ["slow", "fast"], "professions": ["woodcutting"]}}"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let metadata = client.get_item_metadata().await.unwrap();
+        assert_eq!(metadata.identifications.len(), 4);
+        assert_eq!(metadata.filters.subtypes["weapon"], vec!["wand", "spear"]);
+        assert_eq!(metadata.filters.tiers.ingredients, vec![0, 1, 2, 3]);
+
+        let strength = metadata.identification("rawStrength");
+        assert_eq!(strength.name, "Strength");
+        assert_eq!(strength.unit, IdentificationUnit::Raw);
+        assert_eq!(strength.format(7), "+7 Strength");
+        assert_eq!(
+            metadata.identification("manaSteal").format(4),
+            "+4/3s Mana Steal"
+        );
+        assert_eq!(metadata.identification("xpBonus").name, "XP Bonus");
+
+        // Keys the catalogue doesn't know about still get a name and unit.
+        let unknown = metadata.identification("rawMainAttackDamage");
+        assert!(!metadata.has_identification("rawMainAttackDamage"));
+        assert_eq!(unknown.name, "Main Attack Damage");
+        assert_eq!(unknown.unit, IdentificationUnit::Raw);
+        assert_eq!(
+            metadata.identification("walkSpeed").format(-12),
+            "-12% Walk Speed"
+        );
+
+        let query = ItemQuery::new()
+            .item_type("wand")
+            .rarity(ItemRarity::Mythic)
+            .tier(3)
+            .identification("walkSpeed")
+            .attack_speed("slow")
+            .level_range(90, 100);
+        assert!(query.validate(&metadata).is_ok());
+
+        let typo = query.clone().identification("walkSpeeed");
+        match typo.validate(&metadata) {
+            Err(WynnError::InvalidQuery { filter, value }) => {
+                assert_eq!(
+                    (filter.as_str(), value.as_str()),
+                    ("identification", "walkSpeeed")
+                );
+            }
+            other => panic!("expected an invalid query, got {:?}", other),
+        }
+        assert!(query.clone().item_type("wnad").validate(&metadata).is_err());
+        assert!(query
+            .clone()
+            .rarity(ItemRarity::Set)
+            .validate(&metadata)
+            .is_err());
+        assert!(query.clone().tier(7).validate(&metadata).is_err());
+        assert!(query
+            .clone()
+            .attack_speed("slwo")
+            .validate(&metadata)
+            .is_err());
+        assert!(query.level_range(100, 90).validate(&metadata).is_err());
+
+        // Only the metadata request reached the server.
+        assert_eq!(server.requests().len(), 1);
+    }
+}
@@ -1,18 +1,105 @@
+mod common;
+
 #[cfg(test)]
 mod tests {
+    use crate::common::{MockResponse, MockServer};
     use antikythera_rs::*;
 
-    // #[tokio::test]
-    // async fn test_get_leaderboard() {
-    //     match get_leaderboard_types().await {
-    //         Ok(leaderboard_types) => {
-    //             println!("Leaderboard Types: {:?}", leaderboard_types);
-    //         }
-    //         Err(e) => {
-    //             println!("Error: {:?}", e);
-    //         }
-    //     }
-    // }
+    #[tokio::test]
+    async fn test_get_leaderboard() {
+        match get_leaderboard_with_limit("combatGlobalLevel", 10).await {
+            Ok(leaderboard) => {
+                println!("Combat Leaderboard: {:?}", leaderboard);
+            }
+            Err(e) => {
+                println!("Error: {:?}", e);
+            }
+        }
+
+        match get_leaderboard(LeaderboardType::HardcoreContent).await {
+            Ok(leaderboard) => {
+                println!("Hardcore Leaderboard: {:?}", leaderboard);
+            }
+            Err(e) => {
+                println!("Error: {:?}", e);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_leaderboard_metadata() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/leaderboards/hardcoreContent",
+            MockResponse::json(
+                200,
+                r##"{
+                    "2": {"uuid": "uuid-2", "name": "Second", "score": 1500, "previousRanking": 3,
+                        "metadata": {"totalLevel": 1500, "xp": 123456789012, "playtime": 900.5},
+                        "characterUuid": "char-2", "characterType": "MAGE",
+                        "characterData": {"type": "MAGE", "nickname": null, "level": 106, "xp": 0,
+                            "xpPercent": 0, "totalLevel": 1500, "gamemode": ["hardcore"],
+                            "meta": {"died": false}}},
+                    "1": {"uuid": "uuid-1", "name": "First", "score": 1690, "previousRanking": 1,
+                        "rank": "Player", "supportRank": "champion",
+                        "legacyRankColour": {"main": "#ffa214", "sub": "#fff0c3"},
+                        "metadata": {"totalLevel": 1690, "xp": 234567890123, "playtime": 1200},
+                        "characterData": {"type": "ARCHER", "level": 106, "xp": 0, "xpPercent": 0,
+                            "totalLevel": 1690, "gamemode": ["hardcore", "ironman"]}}
+                }"##,
+            ),
+        );
+        server.mock(
+            "/leaderboards/nogCompletion",
+            MockResponse::json(
+                200,
+                r#"{"1": {"uuid": "uuid-1", "name": "First", "score": 400, "metadata": {"playtime": 10}}}"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let leaderboard = client
+            .get_leaderboard_with_limit("hardcoreContent", 2)
+            .await
+            .unwrap();
+        assert_eq!(
+            server.requests()[0].target,
+            "/leaderboards/hardcoreContent?resultLimit=2"
+        );
+        assert_eq!(leaderboard.len(), 2);
+        assert_eq!(leaderboard[0].position, 1);
+        assert_eq!(leaderboard[0].name, "First");
+        assert_eq!(
+            leaderboard[0].legacy_rank_colour.as_ref().unwrap().main,
+            "#ffa214"
+        );
+        match &leaderboard[0].metadata {
+            LeaderboardMetadata::Hardcore {
+                total_level,
+                xp,
+                character,
+                ..
+            } => {
+                assert_eq!(*total_level, 1690);
+                assert_eq!(*xp, 234567890123);
+                assert_eq!(character.class, "ARCHER");
+            }
+            other => panic!("Expected Hardcore metadata, got {:?}", other),
+        }
+
+        let leaderboard = client.get_leaderboard("nogCompletion").await.unwrap();
+        assert_eq!(
+            server.requests()[1].target,
+            "/leaderboards/nogCompletion?resultLimit=100"
+        );
+        assert!(matches!(
+            leaderboard[0].metadata,
+            LeaderboardMetadata::Playtime { .. }
+        ));
+    }
 
     #[tokio::test]
     async fn test_get_leaderboard_types() {
@@ -25,4 +112,209 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_get_guild_leaderboard() {
+        match get_guild_leaderboard_with_limit("guildLevel", 10).await {
+            Ok(leaderboard) => {
+                println!("Guild Level Leaderboard: {:?}", leaderboard);
+            }
+            Err(e) => {
+                println!("Error: {:?}", e);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_guild_leaderboard_entries() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/leaderboards/guildTerritories",
+            MockResponse::json(
+                200,
+                r#"{
+                    "1": {"uuid": "guild-1", "name": "First Guild", "prefix": "FST", "level": 120,
+                        "xp": 98765432100, "territories": 40, "wars": 9000, "members": 150,
+                        "previousRanking": 2},
+                    "2": {"uuid": "guild-2", "name": "Second Guild", "prefix": "SND", "level": 100,
+                        "xp": 1234, "territories": 12, "wars": 20, "members": 80}
+                }"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let leaderboard = client
+            .get_guild_leaderboard("guildTerritories")
+            .await
+            .unwrap();
+        assert_eq!(leaderboard.len(), 2);
+        assert_eq!(leaderboard[0].position, 1);
+        assert_eq!(leaderboard[0].territories, 40);
+        assert_eq!(leaderboard[0].previous_ranking, Some(2));
+        assert_eq!(leaderboard[1].previous_ranking, None);
+
+        let guild = leaderboard[1].guild();
+        assert_eq!(guild.uuid, "guild-2");
+        assert_eq!(guild.name, "Second Guild");
+        assert_eq!(guild.prefix, "SND");
+    }
+
+    #[tokio::test]
+    async fn test_guild_boards_are_not_player_boards() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/leaderboards/fixture",
+            MockResponse::json(
+                200,
+                r#"{"1": {"uuid": "guild-1", "name": "First Guild", "prefix": "FST", "level": 120}}"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        match client.get_leaderboard(LeaderboardType::GuildLevel).await {
+            Err(WynnError::GuildLeaderboard { board }) => assert_eq!(board, "guildLevel"),
+            other => panic!("Expected GuildLeaderboard, got {:?}", other),
+        }
+        match client
+            .get_guild_leaderboard(LeaderboardType::PlayerContent)
+            .await
+        {
+            Err(WynnError::PlayerLeaderboard { board }) => assert_eq!(board, "playerContent"),
+            other => panic!("Expected PlayerLeaderboard, got {:?}", other),
+        }
+        assert_eq!(server.request_count(), 0);
+
+        // Guild entries on a board the client doesn't know are still rejected, not zeroed.
+        match client.get_leaderboard("fixture").await {
+            Err(WynnError::Decode { message, .. }) => assert!(message.contains("score")),
+            other => panic!("Expected Decode, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metadata_follows_the_board() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/leaderboards/combatGlobalLevel",
+            MockResponse::json(
+                200,
+                r#"{"1": {"uuid": "uuid-1", "name": "First", "score": 106,
+                    "metadata": {"totalLevel": 1690, "xp": 1234, "playtime": 10}}}"#,
+            ),
+        );
+        server.mock(
+            "/leaderboards/playerContent",
+            MockResponse::json(
+                200,
+                r#"{"1": {"uuid": "uuid-1", "name": "First", "score": 400,
+                    "metadata": {"totalLevel": null, "xp": 1234, "playtime": 10}}}"#,
+            ),
+        );
+        server.mock(
+            "/leaderboards/hardcoreContent",
+            MockResponse::json(
+                200,
+                r#"{"1": {"uuid": "uuid-1", "name": "First", "score": 400}}"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        // Extra fields don't change the variant a board's entries get.
+        let leaderboard = client.get_leaderboard("combatGlobalLevel").await.unwrap();
+        assert!(matches!(
+            leaderboard[0].metadata,
+            LeaderboardMetadata::Level { xp: 1234, .. }
+        ));
+
+        match client.get_leaderboard(LeaderboardType::PlayerContent).await {
+            Err(WynnError::Decode { path, .. }) => assert_eq!(path, "1.metadata.totalLevel"),
+            other => panic!("Expected Decode, got {:?}", other),
+        }
+        match client
+            .get_leaderboard(LeaderboardType::HardcoreContent)
+            .await
+        {
+            Err(WynnError::Decode { path, .. }) => assert_eq!(path, "1.metadata.playtime"),
+            other => panic!("Expected Decode, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_leaderboard_types() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/leaderboards/types",
+            MockResponse::json(
+                200,
+                r#"["hicContent", "tnaCompletion", "guildWars", "newBoard"]"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let types = client.get_leaderboard_types().await.unwrap();
+        assert_eq!(
+            types,
+            vec![
+                LeaderboardType::HicContent,
+                LeaderboardType::TnaCompletion,
+                LeaderboardType::GuildWars,
+                LeaderboardType::Unknown("newBoard".to_string()),
+            ]
+        );
+        assert_eq!(types[3].as_str(), "newBoard");
+        assert_eq!(types[3].category(), None);
+        assert_eq!(
+            types[1].category(),
+            Some(LeaderboardCategory::RaidCompletion)
+        );
+        assert_eq!(types[2].category(), Some(LeaderboardCategory::Guild));
+
+        for lb_type in LeaderboardType::ALL {
+            assert_eq!(&LeaderboardType::from(lb_type.as_str()), lb_type);
+            assert!(lb_type.category().is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_leaderboard_gamemodes() {
+        assert_eq!(
+            LeaderboardType::HichContent.gamemodes(),
+            &[
+                Gamemode::Hardcore,
+                Gamemode::Ironman,
+                Gamemode::Craftsman,
+                Gamemode::Hunted
+            ]
+        );
+        assert!(LeaderboardType::CombatGlobalLevel.gamemodes().is_empty());
+
+        let gamemodes = vec![
+            "hardcore".to_string(),
+            "ironman".to_string(),
+            "craftsman".to_string(),
+        ];
+        assert_eq!(
+            LeaderboardType::for_gamemodes(&gamemodes),
+            vec![
+                LeaderboardType::HardcoreLegacyLevel,
+                LeaderboardType::HardcoreContent,
+                LeaderboardType::IronmanContent,
+                LeaderboardType::CraftsmanContent,
+                LeaderboardType::HicContent,
+            ]
+        );
+        assert!(LeaderboardType::for_gamemodes(&[]).is_empty());
+    }
 }
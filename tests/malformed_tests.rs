@@ -0,0 +1,290 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::common::{MockResponse, MockServer};
+    use antikythera_rs::*;
+    use futures::StreamExt;
+
+    const ROUTES: [&str; 25] = [
+        "/player",
+        "/player/Fixture",
+        "/player/00000000-0000-0000-0000-000000000001",
+        "/player/Fixture/characters",
+        "/player/Fixture/characters/fixture-uuid",
+        "/guild/Fixture",
+        "/guild/prefix/FIX",
+        "/guild/uuid/0f1e2d3c-4b5a-4987-8654-3210fedcba98",
+        "/guild/list/guild",
+        "/guild/list/territory",
+        "/map/locations/markers",
+        "/map/quests",
+        "/map/player",
+        "/latest-news",
+        "/classes",
+        "/classes/archer",
+        "/leaderboards/types",
+        "/leaderboards/fixture",
+        "/item/database",
+        "/item/search",
+        "/item/quick/fixture",
+        "/item/metadata",
+        "/ability/tree/archer",
+        "/player/Fixture/characters/fixture-uuid/abilities",
+        "/search/fixture",
+    ];
+
+    fn fixtures() -> Vec<(String, String)> {
+        let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/malformed");
+        let mut fixtures: Vec<(String, String)> = std::fs::read_dir(directory)
+            .unwrap()
+            .map(|entry| {
+                let path = entry.unwrap().path();
+                let name = path.file_name().unwrap().to_string_lossy().to_string();
+                (name, std::fs::read_to_string(&path).unwrap())
+            })
+            .collect();
+        fixtures.sort();
+        fixtures
+    }
+
+    // Calls every public endpoint, every route on the server answers with the same body.
+    async fn call_everything(client: WynnClient) -> Vec<(&'static str, Result<()>)> {
+        let player_choice = PlayerChoice {
+            uuid: "00000000-0000-0000-0000-000000000001".to_string(),
+            stored_name: "Fixture".to_string(),
+            rank: None,
+            prefix: None,
+        };
+        let guild_choice = GuildChoice {
+            uuid: "0f1e2d3c-4b5a-4987-8654-3210fedcba98".to_string(),
+            name: "Fixture".to_string(),
+            prefix: "FIX".to_string(),
+        };
+
+        vec![
+            (
+                "get_player_main_stats",
+                client.get_player_main_stats("Fixture").await.map(drop),
+            ),
+            (
+                "get_player_full_stats",
+                client.get_player_full_stats("Fixture").await.map(drop),
+            ),
+            (
+                "get_player_characters",
+                client.get_player_characters("Fixture").await.map(drop),
+            ),
+            (
+                "get_player_character",
+                client
+                    .get_player_character("Fixture", "fixture-uuid")
+                    .await
+                    .map(drop),
+            ),
+            (
+                "resolve_player_choice",
+                client.resolve_player_choice(&player_choice).await.map(drop),
+            ),
+            (
+                "get_online_players",
+                client.get_online_players(false).await.map(drop),
+            ),
+            (
+                "get_online_players_on_world",
+                client.get_online_players_on_world(1, true).await.map(drop),
+            ),
+            (
+                "get_online_player_count",
+                client.get_online_player_count().await.map(drop),
+            ),
+            (
+                "get_online_player_count_on_world",
+                client.get_online_player_count_on_world(1).await.map(drop),
+            ),
+            (
+                "get_online_player_data",
+                client.get_online_player_data(false).await.map(drop),
+            ),
+            ("get_guilds", client.get_guilds(false).await.map(drop)),
+            ("get_guilds_data", client.get_guilds_data().await.map(drop)),
+            (
+                "get_guild",
+                client
+                    .get_guild(&GuildIdentifier::name("Fixture").unwrap())
+                    .await
+                    .map(drop),
+            ),
+            (
+                "get_guild_from_name",
+                client.get_guild_from_name("Fixture").await.map(drop),
+            ),
+            (
+                "get_guild_from_prefix",
+                client.get_guild_from_prefix("FIX").await.map(drop),
+            ),
+            (
+                "resolve_guild_choice",
+                client.resolve_guild_choice(&guild_choice).await.map(drop),
+            ),
+            (
+                "get_territory_data",
+                client.get_territory_data().await.map(drop),
+            ),
+            ("get_map_markers", client.get_map_markers().await.map(drop)),
+            (
+                "get_player_locations",
+                client.get_player_locations().await.map(drop),
+            ),
+            ("get_quest_count", client.get_quest_count().await.map(drop)),
+            ("get_latest_news", client.get_latest_news().await.map(drop)),
+            ("get_classes", client.get_classes().await.map(drop)),
+            ("get_class", client.get_class("archer").await.map(drop)),
+            (
+                "get_leaderboard",
+                client.get_leaderboard("fixture").await.map(drop),
+            ),
+            (
+                "get_guild_leaderboard",
+                client.get_guild_leaderboard("fixture").await.map(drop),
+            ),
+            (
+                "get_item_database_page",
+                client.get_item_database_page(1).await.map(drop),
+            ),
+            (
+                "item_database_pages",
+                client
+                    .item_database_pages()
+                    .take(3)
+                    .collect::<Vec<_>>()
+                    .await
+                    .into_iter()
+                    .collect::<Result<Vec<_>>>()
+                    .map(drop),
+            ),
+            (
+                "get_full_item_database",
+                client.get_full_item_database().await.map(drop),
+            ),
+            (
+                "search_items",
+                client
+                    .search_items(&ItemQuery::new().name("fixture"))
+                    .await
+                    .map(drop),
+            ),
+            (
+                "quick_search_items",
+                client.quick_search_items("fixture").await.map(drop),
+            ),
+            (
+                "get_item_metadata",
+                client.get_item_metadata().await.map(drop),
+            ),
+            (
+                "get_ability_tree",
+                client.get_ability_tree("archer").await.map(drop),
+            ),
+            (
+                "get_character_ability_map",
+                client
+                    .get_character_ability_map("Fixture", "fixture-uuid")
+                    .await
+                    .map(drop),
+            ),
+            ("search", client.search("fixture").await.map(drop)),
+            (
+                "get_leaderboard_types",
+                client.get_leaderboard_types().await.map(drop),
+            ),
+        ]
+    }
+
+    async fn outcomes_for(status: u16, name: &str, body: &str) -> Vec<(&'static str, Result<()>)> {
+        let server = MockServer::start().await;
+        for route in ROUTES {
+            server.mock(route, MockResponse::json(status, body));
+        }
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        // Run on a separate task so a panic is reported against the fixture that caused it.
+        match tokio::spawn(call_everything(client)).await {
+            Ok(outcomes) => outcomes,
+            Err(e) => panic!("{} with status {} panicked: {:?}", name, status, e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_malformed_payloads_return_decode_errors() {
+        for (name, body) in fixtures() {
+            for (function, outcome) in outcomes_for(200, &name, &body).await {
+                match outcome {
+                    Ok(()) | Err(WynnError::Decode { .. }) => {}
+                    Err(e) => panic!("{} returned {:?} for {}", function, e, name),
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_malformed_multiple_choices() {
+        for (name, body) in fixtures() {
+            for (function, outcome) in outcomes_for(300, &name, &body).await {
+                match outcome {
+                    Err(WynnError::MultipleChoices(_)) | Err(WynnError::Decode { .. }) => {}
+                    other => panic!("{} returned {:?} for {}", function, other, name),
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_malformed_error_responses() {
+        for (name, body) in fixtures() {
+            for status in [400, 401, 429, 500] {
+                for (function, outcome) in outcomes_for(status, &name, &body).await {
+                    assert_eq!(
+                        outcome.as_ref().err().and_then(WynnError::status),
+                        Some(status),
+                        "{} returned {:?} for {}",
+                        function,
+                        outcome,
+                        name
+                    );
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_out_of_range_headers() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/map/quests",
+            MockResponse::json(200, r#"{"quests": 262}"#)
+                .header("Cache-Control", "max-age=18446744073709551615")
+                .header("RateLimit-Limit", "18446744073709551615")
+                .header("RateLimit-Remaining", "18446744073709551615")
+                .header("RateLimit-Reset", "18446744073709551615"),
+        );
+        let directory =
+            std::env::temp_dir().join(format!("antikythera-malformed-{}", std::process::id()));
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .cache(CacheConfig::default())
+            .disk_cache(&directory)
+            .build()
+            .unwrap();
+
+        assert_eq!(client.get_quest_count().await.unwrap(), 262);
+        assert_eq!(client.get_quest_count().await.unwrap(), 262);
+
+        let _ = std::fs::remove_dir_all(directory);
+    }
+}
@@ -1,5 +1,8 @@
+mod common;
+
 #[cfg(test)]
 mod tests {
+    use crate::common::{MockResponse, MockServer};
     use antikythera_rs::*;
 
     #[tokio::test]
@@ -27,4 +30,131 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_get_player_locations() {
+        match get_player_locations().await {
+            Ok(players) => {
+                for player in players.iter().take(5) {
+                    println!("{:?}", player);
+                }
+            }
+            Err(e) => {
+                println!("Error: {:?}", e);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_player_locations_and_nearest_markers() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/map/player",
+            MockResponse::json(
+                200,
+                r#"[{"uuid": "a-uuid", "name": "Salted", "server": "WC1", "x": 10, "y": 64, "z": -20,
+                        "friend": true, "party": false},
+                    {"uuid": "b-uuid", "name": "Other", "nickname": "Nick", "server": "WC2",
+                        "x": "-1500", "y": "40", "z": "-1600"}]"#,
+            ),
+        );
+        server.mock(
+            "/map/locations/markers",
+            MockResponse::json(
+                200,
+                r#"[{"name": "Ragni", "icon": "town.png", "x": 0, "y": 70, "z": 0},
+                    {"name": "Detlas", "icon": "town.png", "x": -1000, "y": 70, "z": -1500}]"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .api_key("secret-key")
+            .build()
+            .unwrap();
+
+        let players = client.get_player_locations().await.unwrap();
+        assert_eq!(
+            server.requests()[0].headers["authorization"],
+            "Bearer secret-key"
+        );
+        assert_eq!(players.len(), 2);
+        assert_eq!(players[0].world, "WC1");
+        assert_eq!(
+            (
+                players[0].location.x(),
+                players[0].location.y(),
+                players[0].location.z()
+            ),
+            (10, Some(64), -20)
+        );
+        assert_eq!(
+            (players[0].friend, players[0].party),
+            (Some(true), Some(false))
+        );
+        assert_eq!(players[1].nickname.as_deref(), Some("Nick"));
+        assert_eq!(players[1].friend, None);
+
+        let markers = client.get_map_markers().await.unwrap();
+        let nearest: Vec<(&str, Option<&str>)> = nearest_markers(&players, &markers)
+            .into_iter()
+            .map(|(player, marker)| (player.name.as_str(), marker.map(|m| m.name.as_str())))
+            .collect();
+        assert_eq!(
+            nearest,
+            vec![("Salted", Some("Ragni")), ("Other", Some("Detlas"))]
+        );
+        assert!(players[0].nearest_marker(&[]).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_player_locations_without_coordinates() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/map/player",
+            MockResponse::json(
+                200,
+                r#"[{"uuid": "a-uuid", "name": "Salted", "server": "WC1", "y": 64}]"#,
+            ),
+        );
+        server.mock(
+            "/map/player",
+            MockResponse::json(
+                200,
+                r#"[{"uuid": "a-uuid", "name": "Salted", "server": "WC1",
+                    "x": null, "y": null, "z": null}]"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        match client.get_player_locations().await {
+            Err(WynnError::Decode { message, .. }) => assert!(message.contains("`x`")),
+            other => panic!("expected a decode error, got {:?}", other),
+        }
+        // A null coordinate is as unknown as a missing one, not the origin.
+        match client.get_player_locations().await {
+            Err(WynnError::Decode { path, .. }) => assert_eq!(path, "[0].x"),
+            other => panic!("expected a decode error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_player_locations_need_an_api_key() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/map/player",
+            MockResponse::json(401, r#"{"message": "Authentication required"}"#),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        match client.get_player_locations().await {
+            Err(WynnError::Unauthorized { status, .. }) => assert_eq!(status, 401),
+            other => panic!("expected an unauthorized error, got {:?}", other),
+        }
+    }
 }
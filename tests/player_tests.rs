@@ -1,5 +1,8 @@
+mod common;
+
 #[cfg(test)]
 mod tests {
+    use crate::common::{MockResponse, MockServer};
     use antikythera_rs::*;
 
     #[tokio::test]
@@ -196,4 +199,201 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_get_player_main_stats_multiple_choices() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/player/Salted",
+            MockResponse::json(
+                300,
+                r#"{
+                    "7b3ee4bc-1d2f-4e3a-9c1c-5a7d0c9f1a01": {"storedName": "Salted", "rank": "Player"},
+                    "0c6a6d2e-52f4-4c7c-8d3d-3f6f7c2b9e02": {"storedName": "salted", "rank": "Media", "prefix": "MEDIA"}
+                }"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let choices = match client.get_player_main_stats("Salted").await {
+            Err(WynnError::MultipleChoices(MultipleChoices::Players(choices))) => choices,
+            other => panic!("Expected player choices, got {:?}", other),
+        };
+
+        assert_eq!(choices.len(), 2);
+        let media = choices
+            .iter()
+            .find(|choice| choice.stored_name == "salted")
+            .unwrap();
+        assert_eq!(media.rank.as_deref(), Some("Media"));
+        assert_eq!(media.prefix.as_deref(), Some("MEDIA"));
+
+        let _ = client.resolve_player_choice(media).await;
+        let requests = server.requests();
+        assert_eq!(
+            requests.last().unwrap().target,
+            "/player/0c6a6d2e-52f4-4c7c-8d3d-3f6f7c2b9e02"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_decode_player_characters() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/player/Salted/characters",
+            MockResponse::json(
+                200,
+                r#"{
+                    "char-1": {"type": "MAGE", "nickname": null, "level": 106, "xp": 0,
+                        "xpPercent": 0, "totalLevel": 1690, "gamemode": ["hardcore"],
+                        "meta": {"died": false}},
+                    "char-2": {"type": "ARCHER", "level": 1, "xp": 0, "xpPercent": 0,
+                        "totalLevel": 13, "gamemode": [], "meta": {}}
+                }"#,
+            ),
+        );
+        server.mock(
+            "/player/Broken/characters",
+            MockResponse::json(
+                200,
+                r#"{"char-1": {"type": "MAGE", "level": "high", "xp": 0, "xpPercent": 0,
+                    "totalLevel": 1, "gamemode": []}}"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let characters = client.get_player_characters("Salted").await.unwrap();
+        assert_eq!(characters["char-1"].class, "MAGE");
+        assert_eq!(characters["char-1"].gamemodes, vec!["hardcore"]);
+        assert!(!characters["char-1"].meta.as_ref().unwrap().died);
+        assert!(characters["char-2"].meta.is_none());
+
+        match client.get_player_characters("Broken").await {
+            Err(WynnError::Decode { field, path, .. }) => {
+                assert_eq!(field, "level");
+                assert_eq!(path, "char-1.level");
+            }
+            other => panic!("Expected Decode, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decode_player_character() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/player/Salted/characters/char-1",
+            MockResponse::json(
+                200,
+                r#"{"type": "MAGE", "nickname": "Bonk", "level": 106, "xp": 12, "xpPercent": 40,
+                    "totalLevel": 1690, "wars": 3, "playtime": 120.5, "mobsKilled": 9000,
+                    "chestsFound": 400, "itemsIdentified": 12, "blocksWalked": 5000000,
+                    "logins": 800, "deaths": 20, "discoveries": 500, "preEconomy": false,
+                    "pvp": {"kills": 1, "deaths": 2}, "gamemode": ["ironman"],
+                    "skillPoints": {"strength": 10, "intelligence": 90},
+                    "professions": {"fishing": {"level": 106, "xpPercent": 10}, "mining": {}},
+                    "dungeons": {"total": 4, "list": {"Skeleton": 4}},
+                    "raids": {"total": 1, "list": {"Nest of the Grootslangs": 1}},
+                    "quests": ["King's Recruit"]}"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let character = client
+            .get_player_character("Salted", "char-1")
+            .await
+            .unwrap();
+        assert_eq!(character.class, "MAGE");
+        assert_eq!(character.nickname.as_deref(), Some("Bonk"));
+        assert_eq!(character.gamemodes, vec!["ironman"]);
+        let skill_points = character.skill_points.unwrap();
+        assert_eq!(
+            (skill_points.intelligence, skill_points.agility),
+            (Some(90), None)
+        );
+        let professions = character.professions.unwrap();
+        assert_eq!(professions["fishing"].level, 106);
+        assert_eq!(professions["mining"].level, 1);
+        assert_eq!(character.dungeons.unwrap().dungeon_list["Skeleton"], 4);
+        assert_eq!(character.raids.unwrap().total, 1);
+        assert_eq!(character.quests.unwrap(), vec!["King's Recruit"]);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_restricted_profiles() {
+        let server = MockServer::start().await;
+        for (name, body) in [
+            ("Public", include_str!("fixtures/players/public.json")),
+            ("Partial", include_str!("fixtures/players/partial.json")),
+            ("Private", include_str!("fixtures/players/private.json")),
+        ] {
+            server.mock(&format!("/player/{}", name), MockResponse::json(200, body));
+        }
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let public = client.get_player_full_stats("Public").await.unwrap();
+        let main = &public.main_data;
+        assert!(main.public_profile);
+        assert_eq!((main.online, main.veteran), (Some(true), Some(false)));
+        assert_eq!(main.playtime, Some(1500.5));
+        let global = main.global_data.as_ref().unwrap();
+        assert_eq!(global.wars, Some(0));
+        assert_eq!(global.pvp.as_ref().unwrap().kills, Some(0));
+        assert_eq!(public.characters["char-1"].mobs_killed, Some(200000));
+
+        // Hidden stats are `None`, stats that are really zero stay `Some(0)`.
+        let partial = client.get_player_full_stats("Partial").await.unwrap();
+        let main = &partial.main_data;
+        assert_eq!(
+            main.first_join.as_ref().unwrap(),
+            "2018-01-01T00:00:00.000Z"
+        );
+        assert!(main.playtime.is_none() && main.last_join.is_none());
+        assert!(main.ranking.is_none());
+        let global = main.global_data.as_ref().unwrap();
+        assert_eq!((global.wars, global.total_level), (None, Some(400)));
+        assert!(global.dungeons.is_none());
+        let pvp = global.pvp.as_ref().unwrap();
+        assert_eq!((pvp.kills, pvp.deaths), (None, Some(0)));
+        let character = &partial.characters["char-1"];
+        assert_eq!((character.deaths, character.playtime), (Some(0), None));
+        assert!(character.professions.is_none() && character.quests.is_none());
+        // `{}` means no points allocated, `null` means the section is hidden.
+        let skill_points = character.skill_points.as_ref().unwrap();
+        assert_eq!(
+            (skill_points.strength, skill_points.agility),
+            (Some(0), Some(0))
+        );
+        assert!(partial.characters["char-2"].skill_points.is_none());
+        let skill_points = partial.characters["char-3"].skill_points.as_ref().unwrap();
+        assert_eq!(
+            (
+                skill_points.strength,
+                skill_points.agility,
+                skill_points.defense
+            ),
+            (Some(30), None, None)
+        );
+        assert_eq!((main.online, main.veteran), (Some(false), None));
+
+        let private = client.get_player_main_stats("Private").await.unwrap();
+        assert!(!private.public_profile);
+        assert_eq!(private.online, None);
+        assert!(private.global_data.is_none());
+        assert!(private.first_join.is_none() && private.active_character.is_none());
+        let private = client.get_player_full_stats("Private").await.unwrap();
+        assert!(private.characters.is_empty());
+    }
 }
@@ -0,0 +1,73 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::common::{MockResponse, MockServer};
+    use antikythera_rs::*;
+
+    fn quests(remaining: u32, reset: u64) -> MockResponse {
+        MockResponse::json(200, r#"{"quests": 262}"#)
+            .header("RateLimit-Limit", "120")
+            .header("RateLimit-Remaining", &remaining.to_string())
+            .header("RateLimit-Reset", &reset.to_string())
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_is_tracked_per_bucket() {
+        let server = MockServer::start().await;
+        server.mock("/map/quests", quests(119, 60));
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        assert!(client.rate_limit("map").is_none());
+        client.get_quest_count().await.unwrap();
+
+        let limit = client.rate_limit("map").unwrap();
+        assert_eq!(limit.limit, 120);
+        assert_eq!(limit.remaining, 119);
+        assert!(limit.reset_in() <= Duration::from_secs(60));
+        assert!(client.rate_limit("player").is_none());
+        assert_eq!(client.rate_limits().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_wait_on_rate_limit() {
+        let server = MockServer::start().await;
+        server.mock("/map/quests", quests(0, 1));
+        server.mock("/map/quests", quests(119, 60));
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .wait_on_rate_limit(true)
+            .build()
+            .unwrap();
+
+        client.get_quest_count().await.unwrap();
+        let start = Instant::now();
+        client.get_quest_count().await.unwrap();
+
+        assert!(start.elapsed() >= Duration::from_millis(900));
+        assert_eq!(server.request_count(), 2);
+        assert_eq!(client.rate_limit("map").unwrap().remaining, 119);
+    }
+
+    #[tokio::test]
+    async fn test_no_wait_by_default() {
+        let server = MockServer::start().await;
+        server.mock("/map/quests", quests(0, 30));
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        client.get_quest_count().await.unwrap();
+        let start = Instant::now();
+        client.get_quest_count().await.unwrap();
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(server.request_count(), 2);
+    }
+}
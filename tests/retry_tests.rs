@@ -0,0 +1,126 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::common::{MockResponse, MockServer};
+    use antikythera_rs::*;
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy::default()
+            .base_delay(Duration::from_millis(10))
+            .jitter(false)
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors() {
+        let server = MockServer::start().await;
+        server.mock("/map/quests", MockResponse::json(503, ""));
+        server.mock("/map/quests", MockResponse::json(502, ""));
+        server.mock("/map/quests", MockResponse::json(200, r#"{"quests": 262}"#));
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .retry_policy(fast_policy())
+            .build()
+            .unwrap();
+
+        assert_eq!(client.get_quest_count().await.unwrap(), 262);
+        assert_eq!(server.request_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let server = MockServer::start().await;
+        server.mock("/map/quests", MockResponse::json(500, ""));
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .retry_policy(fast_policy().max_attempts(2))
+            .build()
+            .unwrap();
+
+        match client.get_quest_count().await {
+            Err(WynnError::ServerError { status }) => assert_eq!(status, 500),
+            other => panic!("Expected ServerError, got {:?}", other),
+        }
+        assert_eq!(server.request_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_delays_double_between_retries() {
+        let server = MockServer::start().await;
+        server.mock("/map/quests", MockResponse::json(500, ""));
+        let base_delay = Duration::from_millis(100);
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .retry_policy(
+                RetryPolicy::default()
+                    .max_attempts(4)
+                    .base_delay(base_delay)
+                    .jitter(false),
+            )
+            .build()
+            .unwrap();
+
+        assert!(client.get_quest_count().await.is_err());
+        let requests = server.requests();
+        let delays: Vec<Duration> = requests
+            .windows(2)
+            .map(|pair| pair[1].received_at - pair[0].received_at)
+            .collect();
+        assert_eq!(delays.len(), 3);
+
+        // The first retry waits the base delay, then each wait doubles.
+        assert!(delays[0] >= base_delay && delays[0] < base_delay * 2);
+        assert!(delays[1] >= base_delay * 2);
+        assert!(delays[2] >= base_delay * 4);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_not_found() {
+        let server = MockServer::start().await;
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .retry_policy(fast_policy())
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            client.get_quest_count().await,
+            Err(WynnError::NotFound)
+        ));
+        assert_eq!(server.request_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_honours_retry_after() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/map/quests",
+            MockResponse::json(429, "{}").header("Retry-After", "1"),
+        );
+        server.mock("/map/quests", MockResponse::json(200, r#"{"quests": 262}"#));
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .retry_policy(fast_policy())
+            .build()
+            .unwrap();
+
+        let start = Instant::now();
+        assert_eq!(client.get_quest_count().await.unwrap(), 262);
+        assert!(start.elapsed() >= Duration::from_millis(900));
+    }
+
+    #[tokio::test]
+    async fn test_no_retries_by_default() {
+        let server = MockServer::start().await;
+        server.mock("/map/quests", MockResponse::json(503, ""));
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        assert!(client.get_quest_count().await.is_err());
+        assert_eq!(server.request_count(), 1);
+    }
+}
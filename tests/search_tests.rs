@@ -0,0 +1,90 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockResponse, MockServer};
+    use antikythera_rs::*;
+
+    #[tokio::test]
+    async fn test_search() {
+        match search("Avicia").await {
+            Ok(results) => {
+                println!("Search Results: {:?}", results);
+            }
+            Err(e) => {
+                println!("Error: {:?}", e);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decode_search_and_resolve() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/search/Av",
+            MockResponse::json(
+                200,
+                r#"{"query": "Av",
+                    "players": {"1ed075fba4214b0d8b5de5d7ef4b5d4e": "Avocado"},
+                    "guilds": {"g-uuid": {"name": "Avicia", "prefix": "AVO"}},
+                    "guildsPrefix": {"7c9e6679-7425-40de-944b-e07fc1f90ae7":
+                        {"name": "Aviators", "prefix": "Av"}},
+                    "territories": {"Avos Temple": {"start": [100, -200], "end": [150, -250]}},
+                    "discoveries": {"Avos Workshop": {"start": [10, 20], "end": [30, 40]}}}"#,
+            ),
+        );
+        server.mock(
+            "/player/1ed075fb-a421-4b0d-8b5d-e5d7ef4b5d4e",
+            MockResponse::json(200, r#"{"username": "Avocado"}"#),
+        );
+        server.mock(
+            "/guild/uuid/7c9e6679-7425-40de-944b-e07fc1f90ae7",
+            MockResponse::json(200, r#"{"name": "Aviators"}"#),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let results = client.search("Av").await.unwrap();
+        assert!(!results.is_empty());
+        assert_eq!(results.query, "Av");
+        assert_eq!(results.players[0].uuid, "1ed075fba4214b0d8b5de5d7ef4b5d4e");
+        assert_eq!(results.players[0].stored_name, "Avocado");
+        assert_eq!(results.guilds[0].prefix, "AVO");
+        assert_eq!(results.guild_prefixes[0].name, "Aviators");
+
+        let temple = &results.territories[0];
+        assert_eq!(temple.name, "Avos Temple");
+        assert_eq!(
+            (temple.start_location.x(), temple.start_location.z()),
+            (100, -200)
+        );
+        assert_eq!(results.discoveries[0].end_location.x(), 30);
+
+        // Hits go through the same resolvers as multiple choice responses.
+        let _ = client.resolve_player_choice(&results.players[0]).await;
+        let _ = client
+            .resolve_guild_choice(&results.guild_prefixes[0])
+            .await;
+        let targets: Vec<String> = server
+            .requests()
+            .iter()
+            .map(|request| request.target.clone())
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                "/search/Av",
+                "/player/1ed075fb-a421-4b0d-8b5d-e5d7ef4b5d4e",
+                "/guild/uuid/7c9e6679-7425-40de-944b-e07fc1f90ae7?identifier=uuid"
+            ]
+        );
+
+        server.mock(
+            "/search/zzz",
+            MockResponse::json(200, r#"{"query": "zzz"}"#),
+        );
+        assert!(client.search("zzz").await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::common::{MockResponse, MockServer};
+    use antikythera_rs::*;
+
+    #[test]
+    fn test_parse_timestamps() {
+        let seconds = |raw: &str| {
+            Timestamp::from(raw)
+                .to_system_time()
+                .map(|time| time.duration_since(UNIX_EPOCH).unwrap())
+        };
+
+        assert_eq!(seconds("1970-01-01T00:00:00Z"), Some(Duration::ZERO));
+        assert_eq!(
+            seconds("2013-05-24T10:21:01.000Z"),
+            Some(Duration::from_secs(1369390861))
+        );
+        assert_eq!(
+            seconds("2024-04-28T15:44:40.560000Z"),
+            Some(Duration::new(1714319080, 560_000_000))
+        );
+        assert_eq!(
+            seconds("2024-02-29T02:00:00+02:00"),
+            Some(Duration::from_secs(1709164800))
+        );
+
+        for invalid in [
+            "",
+            "2024-02-29T00:00:00",
+            "2024-02-29T00:00:00.5",
+            "April 05 2024",
+            "2023-02-29T00:00:00Z",
+            "2024-13-01T00:00:00Z",
+            "2024-01-01T24:00:00Z",
+            "2024-01-01T00:00:00.Z",
+            "2024-01-01T00:00:00+0200",
+            "2024-01-01T00:00:00+é:00",
+        ] {
+            assert_eq!(seconds(invalid), None, "{}", invalid);
+            // The original text is kept either way.
+            assert_eq!(Timestamp::from(invalid), invalid);
+        }
+        // Only chrono reads the looser forms RFC 3339 allows, such as a space separator.
+        #[cfg(not(feature = "chrono"))]
+        assert_eq!(seconds("2024-02-29 00:00:00Z"), None);
+    }
+
+    #[test]
+    fn test_elapsed() {
+        let acquired = Timestamp::from("2024-04-28T12:00:00Z");
+        let now = UNIX_EPOCH + Duration::from_secs(1714305600 + 3 * 86400 + 3600);
+
+        assert_eq!(
+            acquired.elapsed_since(now),
+            Some(Duration::from_secs(3 * 86400 + 3600))
+        );
+        assert_eq!(
+            Timestamp::from("2099-01-01T00:00:00Z").elapsed_since(now),
+            None
+        );
+        assert!(acquired.days_since().unwrap() > 365);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_datetime() {
+        use chrono::{Datelike, Timelike};
+
+        let datetime = Timestamp::from("2024-04-28T17:44:40.560+02:00")
+            .to_datetime()
+            .unwrap();
+        assert_eq!((datetime.year(), datetime.hour()), (2024, 15));
+        assert!(Timestamp::from("April 05 2024").to_datetime().is_none());
+
+        // Both accessors read the same parse, offsets included or not.
+        for raw in [
+            "2024-02-29T00:00:00",
+            "2024-04-28T15:44:40.560000Z",
+            "2024-13-01",
+        ] {
+            let timestamp = Timestamp::from(raw);
+            assert_eq!(
+                timestamp.to_datetime().map(std::time::SystemTime::from),
+                timestamp.to_system_time(),
+                "{}",
+                raw
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decoded_timestamps() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/guild/list/territory",
+            MockResponse::json(
+                200,
+                r#"{"Ragni": {"guild": {"uuid": "g", "name": "Salt Mines", "prefix": "SALT"},
+                    "acquired": "2024-04-28T15:44:40.560000Z",
+                    "location": {"start": [0, 0], "end": [10, 10]}},
+                   "Detlas": {"guild": {"uuid": "g", "name": "Salt Mines", "prefix": "SALT"},
+                    "location": {"start": [20, 20], "end": [30, 30]}}}"#,
+            ),
+        );
+        server.mock(
+            "/player/Salted",
+            MockResponse::json(
+                200,
+                r#"{"username": "Salted", "uuid": "u", "firstJoin": "2013-05-24T10:21:01.000Z",
+                    "lastJoin": "2024-09-01T18:00:00.000Z"}"#,
+            ),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let territories = client.get_territory_data().await.unwrap();
+        let territory = |name: &str| {
+            territories
+                .iter()
+                .find(|territory| territory.territory_name == name)
+                .unwrap()
+        };
+        assert_eq!(
+            territory("Ragni").acquired.as_ref().unwrap(),
+            "2024-04-28T15:44:40.560000Z"
+        );
+        assert!(territory("Ragni").held_for().unwrap() > Duration::from_secs(86400));
+        // A missing date stays missing rather than becoming an empty one.
+        assert!(territory("Detlas").acquired.is_none());
+
+        let player = client.get_player_main_stats("Salted").await.unwrap();
+        assert_eq!(
+            player.first_join.as_ref().unwrap().as_str(),
+            "2013-05-24T10:21:01.000Z"
+        );
+        assert!(player.days_since_last_join().unwrap() > 0);
+    }
+}
@@ -0,0 +1,139 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{MockResponse, MockServer};
+    use antikythera_rs::*;
+
+    // The server answers 404 to anything not mocked, only the targets that were sent matter here.
+    async fn targets(calls: impl AsyncFnOnce(&WynnClient)) -> Vec<String> {
+        let server = MockServer::start().await;
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        calls(&client).await;
+
+        server
+            .requests()
+            .into_iter()
+            .map(|request| request.target)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_guild_names_are_encoded() {
+        let sent = targets(async |client: &WynnClient| {
+            for name in ["Salt Mines", "A/B #1?", "50% Off", "Ünïcødé 公会"] {
+                let identifier = GuildIdentifier::name(name).unwrap();
+                let _ = client.get_guild(&identifier).await;
+            }
+            let _ = client.get_guild_from_name("Salt Mines").await;
+        })
+        .await;
+
+        assert_eq!(
+            sent,
+            vec![
+                "/guild/Salt%20Mines?identifier=uuid",
+                "/guild/A%2FB%20%231%3F?identifier=uuid",
+                "/guild/50%25%20Off?identifier=uuid",
+                "/guild/%C3%9Cn%C3%AFc%C3%B8d%C3%A9%20%E5%85%AC%E4%BC%9A?identifier=uuid",
+                "/guild/Salt%20Mines?identifier=uuid",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_free_text_segments_are_encoded() {
+        let sent = targets(async |client: &WynnClient| {
+            let _ = client.quick_search_items("Bob's Tear").await;
+            let _ = client.search("a&b=c?d").await;
+            let _ = client.get_class("../news").await;
+            let _ = client.get_leaderboard("odd/board name").await;
+        })
+        .await;
+
+        assert_eq!(
+            sent,
+            vec![
+                "/item/quick/Bob's%20Tear",
+                "/search/a&b=c%3Fd",
+                "/classes/..%2Fnews",
+                "/leaderboards/odd%2Fboard%20name?resultLimit=100",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dot_segments_are_rejected() {
+        let sent = targets(async |client: &WynnClient| {
+            for class in [".", "..", ""] {
+                match client.get_class(class).await {
+                    Err(WynnError::InvalidIdentifier { kind, value }) => {
+                        assert_eq!((kind.as_str(), value.as_str()), ("path segment", class));
+                    }
+                    other => panic!("expected {:?} to be rejected, got {:?}", class, other),
+                }
+            }
+
+            // Empty segments would request the listing one level up.
+            let rejected =
+                |result: Result<()>| matches!(result, Err(WynnError::InvalidIdentifier { .. }));
+            assert!(rejected(
+                client.get_player_character("Salted", "").await.map(drop)
+            ));
+            assert!(rejected(
+                client
+                    .get_character_ability_map("Salted", "")
+                    .await
+                    .map(drop)
+            ));
+            assert!(rejected(client.search("").await.map(drop)));
+            assert!(rejected(client.quick_search_items("").await.map(drop)));
+        })
+        .await;
+
+        assert!(sent.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_queries_are_unchanged() {
+        let sent = targets(async |client: &WynnClient| {
+            let _ = client.get_online_players_on_world(12, true).await;
+            let _ = client.get_online_player_count().await;
+            let _ = client.get_item_database_page(3).await;
+            let _ = client.get_full_item_database().await;
+            let _ = client.get_player_full_stats("Salted").await;
+        })
+        .await;
+
+        assert_eq!(
+            sent,
+            vec![
+                "/player?identifier=uuid&server=WC12",
+                "/player",
+                "/item/database?page=3",
+                "/item/database?fullResult=True",
+                "/player/Salted?fullResult=True",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_encoded_routes_resolve() {
+        let server = MockServer::start().await;
+        server.mock(
+            "/item/quick/Bob's%20Tear",
+            MockResponse::json(200, r#"{"Bob's Tear": {"type": "ingredient", "tier": 3}}"#),
+        );
+        let client = WynnClient::builder()
+            .base_url(server.base_url())
+            .build()
+            .unwrap();
+
+        let items = client.quick_search_items("Bob's Tear").await.unwrap();
+        assert_eq!(items[0].name, "Bob's Tear");
+    }
+}